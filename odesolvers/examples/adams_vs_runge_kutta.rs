@@ -0,0 +1,73 @@
+use std::cell::Cell;
+
+use odesolvers::error::SolverError;
+use odesolvers::plot::Plot;
+use odesolvers::runge_kutta::Integrator;
+
+const PLOT_WIDTH: usize = 220;
+const PLOT_HEIGHT: usize = 70;
+
+fn main() -> Result<(), SolverError<f64>> {
+    let dt = 0.01;
+    let final_time = 60.;
+    let initial_state = [1., -C / (2. * M)];
+
+    // counts how often each solver calls the dynamics
+    let evaluations = Cell::new(0);
+    let dynamics = |state: &[f64; 2]| {
+        evaluations.set(evaluations.get() + 1);
+        damped_oscillator_dynamics(state)
+    };
+
+    let mut plot = Plot::build(PLOT_HEIGHT, PLOT_WIDTH);
+    plot.xbounds(-2., 60.).ybounds(-1.2, 1.2).set_settings().subtick(true).subtick_spacing(2.);
+    plot.apply_settings();
+
+    let mut fixed = Integrator::build(initial_state, dt, dynamics);
+    let output = fixed.solve_with_time(final_time)?;
+    report("rk4", &output, evaluations.replace(0));
+
+    let mut dynamic = Integrator::build(initial_state, dt, dynamics);
+    dynamic.set_control().absolute_tolerance(1e-10).relative_tolerance(1e-10);
+    let output = dynamic.solve_dynamic_with_time(final_time)?;
+    report("dormand prince", &output, evaluations.replace(0));
+
+    let mut adams = Integrator::build(initial_state, dt, dynamics);
+    adams.set_control().absolute_tolerance(1e-10).relative_tolerance(1e-10);
+    let output = adams.solve_adams_with_time(final_time)?;
+    report("adams", &output, evaluations.replace(0));
+    println!("adams finished at order {}", adams.adams_order());
+
+    plot.set_brush().front_color(0, 0, 255);
+    output.windows(2).for_each(|window| {
+        let ((t0, start), (t1, end)) = (window[0], window[1]);
+        plot.plot_line(t0, start[0], t1, end[0]);
+    });
+    plot.display();
+    println!("damped oscillator integrated with adams-bashforth-moulton");
+
+    Ok(())
+}
+
+fn report(name: &str, output: &[(f64, [f64; 2])], evaluations: usize) {
+    let error = output.iter().fold(0., |worst: f64, &(time, [x, _])| worst.max((x - exact(time)).abs()));
+    println!("{name}: {} steps, {evaluations} evaluations, worst error {error:e}", output.len());
+}
+
+const C: f64 = 0.2;
+const K: f64 = 2.;
+const M: f64 = 1.;
+
+fn exact(time: f64) -> f64 {
+    let frequency = (K / M - C * C / (4. * M * M)).sqrt();
+    (-C / (2. * M) * time).exp() * (frequency * time).cos()
+}
+
+#[rustfmt::skip]
+fn damped_oscillator_dynamics(state: &[f64; 2]) -> [f64; 2] {
+    let [x, v] = *state;
+    [
+        v,
+        -K / M * x + -C / M * v,
+    ]
+}
@@ -1,10 +1,11 @@
+use odesolvers::error::SolverError;
 use odesolvers::plot::Plot;
 use odesolvers::runge_kutta::Integrator;
 
 const PLOT_WIDTH: usize = 220;
 const PLOT_HEIGHT: usize = 70;
 
-fn main() {
+fn main() -> Result<(), SolverError<f64>> {
     let dt = 0.1;
     let final_time = 17.;
     let initial_state = [10., -3.];
@@ -18,7 +19,7 @@ fn main() {
 
     // numerical solution
     while integrator.curr_time() < final_time {
-        states.push(integrator.dynamic_step());
+        states.push(integrator.dynamic_step()?);
         times.push(integrator.curr_time());
         states.windows(2).zip(times.windows(2)).for_each(|(window, time)| {
             let (start, end) = (window[0], window[1]);
@@ -43,7 +44,19 @@ fn main() {
         plot.clear_string();
     });
 
+    // dense output sampled at uniform times, regardless of where the adaptive steps landed
+    let mut integrator = Integrator::build(initial_state, dt, oscillator_dynamics);
+    let solution = integrator.solve_dynamic_dense(final_time)?;
+    let max_error = solution
+        .sample(points)
+        .iter()
+        .map(|&(t, state)| (state[0] - oscillator_analytical(t, &initial_state)).abs())
+        .fold(0., f64::max);
+
     println!("analytical vs numerical solution for under-damped harmonic oscialltor");
+    println!("max dense output error over {points} uniform samples: {max_error:e}");
+
+    Ok(())
 }
 
 const C: f64 = 0.55;
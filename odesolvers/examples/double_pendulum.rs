@@ -96,8 +96,8 @@ fn pendulum_state_to_cartesian(state: &[f32; 4]) -> [f32; 4] {
     let [t1, t2, ..] = *state;
     [
         L1 * t1.sin(),
-        L1 * t1.cos() * -1.,
+        -(L1 * t1.cos()),
         L1 * t1.sin() + L2 * t2.sin(),
-        L1 * t1.cos() * -1. - L2 * t2.cos(),
+        -(L1 * t1.cos()) - L2 * t2.cos(),
     ]
 }
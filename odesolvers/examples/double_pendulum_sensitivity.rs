@@ -0,0 +1,81 @@
+use std::f64::consts::PI;
+
+use odesolvers::error::SolverError;
+use odesolvers::plot::Plot;
+use odesolvers::sensitivity::SensitivityIntegrator;
+
+const PLOT_WIDTH: usize = 220;
+const PLOT_HEIGHT: usize = 70;
+
+const PARAMETER_NAMES: [&str; 4] = ["m1", "m2", "l1", "l2"];
+const STATE_NAMES: [&str; 4] = ["theta1", "theta2", "omega1", "omega2"];
+
+fn main() -> Result<(), SolverError<f64>> {
+    let final_time = 10.;
+    let initial_state = [PI / 4., PI / 3., 0., 0.];
+    let parameters = [3., 2.5, 12., 8.];
+
+    let mut integrator =
+        SensitivityIntegrator::build(initial_state, parameters, 0.01, double_pendulum_dynamics);
+    integrator.set_control().absolute_tolerance(1e-10).relative_tolerance(1e-8);
+    let output = integrator.solve_dynamic_with_time(final_time)?;
+
+    // how far the outer angle moves per unit change of each initial angle, over time
+    let mut plot = Plot::build(PLOT_HEIGHT, PLOT_WIDTH);
+    plot.xbounds(-0.2, final_time as f32).ybounds(-8., 8.).set_settings().subtick(true).subtick_spacing(0.5);
+    plot.apply_settings();
+    for (column, (red, green, blue)) in [(0, (0, 0, 255)), (1, (255, 0, 0))] {
+        plot.set_brush().front_color(red, green, blue);
+        output.windows(2).for_each(|window| {
+            let (start, end) = (&window[0], &window[1]);
+            let (y0, y1) = (start.state_sensitivity[1][column], end.state_sensitivity[1][column]);
+            plot.plot_line(start.time, y0, end.time, y1);
+        });
+    }
+    plot.display();
+    println!("d theta2 / d theta1(0) (blue) and d theta2 / d theta2(0) (red)");
+
+    let end = output[output.len() - 1];
+    println!("sensitivities of the end state at t = {}", end.time);
+    (0..4).for_each(|row| {
+        let initial: Vec<String> = (0..2)
+            .map(|col| format!("{}(0) {:>9.4}", STATE_NAMES[col], end.state_sensitivity[row][col]))
+            .collect();
+        let physical: Vec<String> = (0..4)
+            .map(|col| format!("{} {:>9.4}", PARAMETER_NAMES[col], end.parameter_sensitivity[row][col]))
+            .collect();
+        println!("{:>7}: {} | {}", STATE_NAMES[row], initial.join("  "), physical.join("  "));
+    });
+
+    Ok(())
+}
+
+const G: f64 = 9.81;
+const C1: f64 = 0.001;
+const C2: f64 = 0.01;
+
+fn double_pendulum_dynamics(state: &[f64; 4], parameters: &[f64; 4]) -> [f64; 4] {
+    let [t1, t2, w1, w2] = *state;
+    let [m1, m2, l1, l2] = *parameters;
+
+    let t1ddt = w1;
+    let t2ddt = w2;
+
+    let den_partial = 2. * m1 + m2 - m2 * (2. * t1 - 2. * t2).cos();
+
+    let num1 = -G * (2. * m1 + m2) * t1.sin()
+        - m2 * G * (t1 - 2. * t2).sin()
+        - 2. * (t1 - t2).sin() * m2 * (w2 * w2 * l2 + w1 * w1 * l1 * (t1 - t2).cos());
+    let mut w1ddt = num1 / (l1 * den_partial);
+    // linear damping model
+    w1ddt -= C1 * w1;
+
+    let num2 = 2.
+        * (t1 - t2).sin()
+        * (w1 * w1 * l1 * (m1 + m2) + G * (m1 + m2) * t1.cos() + w2 * w2 * l2 * m2 * (t1 - t2).cos());
+    let mut w2ddt = num2 / (l2 * den_partial);
+    // linear damping model
+    w2ddt -= C2 * w2;
+
+    [t1ddt, t2ddt, w1ddt, w2ddt]
+}
@@ -0,0 +1,49 @@
+use odesolvers::error::SolverError;
+use odesolvers::event::Direction;
+use odesolvers::event::Event;
+use odesolvers::plot::Plot;
+use odesolvers::runge_kutta::Integrator;
+
+const PLOT_WIDTH: usize = 220;
+const PLOT_HEIGHT: usize = 70;
+
+fn main() -> Result<(), SolverError<f64>> {
+    let periods = 3000.;
+    let period = 2. * std::f64::consts::PI / OMEGA;
+    let mut integrator = Integrator::build_driven([1., 0.], 0.05, duffing_dynamics);
+    integrator.set_control().absolute_tolerance(1e-9).relative_tolerance(1e-9).max_steps(1_000_000);
+
+    // once per forcing period, where the phase of the drive passes zero
+    let section = Event::build(|time: f64, _: &[f64; 2]| (OMEGA * time).sin()).direction(Direction::Rising);
+    let crossings = integrator.solve_dynamic_section(periods * period, section)?;
+
+    let mut plot = Plot::build(PLOT_HEIGHT, PLOT_WIDTH);
+    plot.xbounds(-2., 2.).ybounds(-1.2, 1.2).set_settings().subtick(true).subtick_spacing(0.1);
+    plot.apply_settings();
+    plot.set_brush().front_color(0, 0, 255);
+    // skip the transient before the orbit settles onto the attractor
+    let plotted =
+        plot.plot_scatter(crossings.iter().skip(50).map(|&(_, [position, velocity])| (position, velocity)));
+    plot.display();
+    println!(
+        "stroboscopic section of the forced duffing oscillator, {plotted} of {} crossings",
+        crossings.len()
+    );
+
+    Ok(())
+}
+
+const DELTA: f64 = 0.3;
+const ALPHA: f64 = -1.;
+const BETA: f64 = 1.;
+const GAMMA: f64 = 0.5;
+const OMEGA: f64 = 1.2;
+
+#[rustfmt::skip]
+fn duffing_dynamics(time: f64, state: &[f64; 2]) -> [f64; 2] {
+    let [x, v] = *state;
+    [
+        v,
+        -DELTA * v - ALPHA * x - BETA * x * x * x + GAMMA * (OMEGA * time).cos(),
+    ]
+}
@@ -1,10 +1,11 @@
+use odesolvers::error::SolverError;
 use odesolvers::plot::Plot;
 use odesolvers::runge_kutta::Integrator;
 
 const PLOT_WIDTH: usize = 220;
 const PLOT_HEIGHT: usize = 70;
 
-fn main() {
+fn main() -> Result<(), SolverError<f32>> {
     let dt = 0.05;
     let final_time = 120.;
     let initial_state = [0., -25.];
@@ -21,7 +22,7 @@ fn main() {
     while fixedegrator.curr_time() < final_time || dyanmicgrator.curr_time() < final_time {
         fixed.push(fixedegrator.step());
         fixed_times.push(fixedegrator.curr_time());
-        dynamic.push(dyanmicgrator.dynamic_step());
+        dynamic.push(dyanmicgrator.dynamic_step()?);
         dynamic_times.push(dyanmicgrator.curr_time());
 
         plot.set_brush().front_color(10, 10, 255);
@@ -42,6 +43,8 @@ fn main() {
     }
 
     println!("fixed vs dynamic step size comparison");
+
+    Ok(())
 }
 
 const C: f32 = 0.15;
@@ -0,0 +1,58 @@
+use std::f64::consts::PI;
+
+use odesolvers::error::SolverError;
+use odesolvers::plot::Plot;
+use odesolvers::runge_kutta::Integrator;
+
+const PLOT_WIDTH: usize = 220;
+const PLOT_HEIGHT: usize = 70;
+
+fn main() -> Result<(), SolverError<f64>> {
+    let eccentricity: f64 = 0.5;
+    let final_time = 4. * PI;
+    let initial_state = [1. - eccentricity, 0., 0., ((1. + eccentricity) / (1. - eccentricity)).sqrt()];
+
+    // tight tolerance extrapolation gives the reference the fixed step results are checked against
+    let mut reference = Integrator::build(initial_state, 0.01, kepler_dynamics);
+    reference.set_control().absolute_tolerance(1e-14).relative_tolerance(1e-14);
+    let orbit = reference.solve_extrapolation_with_time(final_time)?;
+    let [x, y, ..] = reference.state();
+    println!(
+        "reference after {} steps, {} columns: ({x:.12}, {y:.12})",
+        orbit.len(),
+        reference.extrapolation_columns()
+    );
+
+    for dt in [0.02, 0.01, 0.005, 0.0025] {
+        let mut integrator = Integrator::build(initial_state, dt, kepler_dynamics);
+        integrator.solve_until(final_time)?;
+        let [x_fixed, y_fixed, ..] = integrator.state();
+        let error = ((x_fixed - x).powi(2) + (y_fixed - y).powi(2)).sqrt();
+        println!("rk4 with dt = {dt}: position error {error:e}");
+    }
+
+    let mut plot = Plot::build(PLOT_HEIGHT, PLOT_WIDTH);
+    plot.xbounds(-2.5, 1.).ybounds(-1.2, 1.2).set_settings().subtick(true).subtick_spacing(0.1);
+    plot.apply_settings();
+    plot.set_brush().front_color(0, 0, 255);
+    orbit.windows(2).for_each(|window| {
+        let ((_, start), (_, end)) = (window[0], window[1]);
+        plot.plot_line(start[0], start[1], end[0], end[1]);
+    });
+    plot.display();
+    println!("kepler orbit from gragg-bulirsch-stoer extrapolation");
+
+    Ok(())
+}
+
+#[rustfmt::skip]
+fn kepler_dynamics(state: &[f64; 4]) -> [f64; 4] {
+    let [x, y, x_dot, y_dot] = *state;
+    let cubed = (x * x + y * y).powf(1.5);
+    [
+        x_dot,
+        y_dot,
+        -x / cubed,
+        -y / cubed,
+    ]
+}
@@ -1,3 +1,4 @@
+use odesolvers::error::SolverError;
 use odesolvers::plot::color_gradient;
 use odesolvers::plot::Plot;
 use odesolvers::runge_kutta::Integrator;
@@ -5,7 +6,7 @@ use odesolvers::runge_kutta::Integrator;
 const PLOT_WIDTH: usize = 220;
 const PLOT_HEIGHT: usize = 70;
 
-fn main() {
+fn main() -> Result<(), SolverError<f32>> {
     let dt = 0.05;
     let final_time = 120.;
     let initial_state = [10., -10.];
@@ -17,7 +18,7 @@ fn main() {
     plot.xbounds(-10., 120.).ybounds(-10., 10.).set_settings().subtick(true).subtick_spacing(2.);
 
     while integrator.curr_time() < final_time {
-        states.push(integrator.dynamic_step());
+        states.push(integrator.dynamic_step()?);
         times.push(integrator.curr_time());
         states.windows(2).zip(times.windows(2)).for_each(|(window, time)| {
             let (start, end) = (window[0], window[1]);
@@ -31,6 +32,8 @@ fn main() {
     }
 
     println!("harmonic oscillator example");
+
+    Ok(())
 }
 
 const C: f32 = 0.1;
@@ -1,22 +1,25 @@
+use odesolvers::error::SolverError;
 use odesolvers::plot::color_gradient;
 use odesolvers::plot::Plot;
 use odesolvers::runge_kutta::Integrator;
+use odesolvers::system::OdeSystem;
 
 const PLOT_WIDTH: usize = 220;
 const PLOT_HEIGHT: usize = 70;
 
-fn main() {
+fn main() -> Result<(), SolverError<f64>> {
     let dt = 0.1;
     let final_time = 120.;
     let initial_state = [0.001, 0.001, 0.001];
-    let mut integrator = Integrator::build(initial_state, dt, lorenz_dynamics);
+    let lorenz = Lorenz { sigma: 10., rho: 28., beta: 8. / 3. };
+    let mut integrator = Integrator::build_system(initial_state, dt, lorenz);
 
     let mut states = Vec::new();
     let mut plot = Plot::build(PLOT_HEIGHT, PLOT_WIDTH);
     plot.xbounds(-30., 30.).ybounds(-3., 60.).set_settings().subtick(true).subtick_spacing(3.);
 
     while integrator.curr_time() < final_time {
-        states.push(integrator.dynamic_step());
+        states.push(integrator.dynamic_step()?);
         states.windows(2).enumerate().for_each(|(time, window)| {
             let (start, end) = (window[0], window[1]);
             let (red, green, blue) = color_gradient(0.001 * time as f32);
@@ -28,18 +31,24 @@ fn main() {
     }
 
     println!("lorenz attractor example");
+
+    Ok(())
+}
+
+struct Lorenz {
+    sigma: f64,
+    rho: f64,
+    beta: f64,
 }
 
-const SIGMA: f64 = 10.;
-const RHO: f64 = 28.;
-const BETA: f64 = 8. / 3.;
-
-#[rustfmt::skip]
-fn lorenz_dynamics(state: &[f64; 3]) -> [f64; 3] {
-    let [x, y, z] = state;
-    [
-        SIGMA * (y - x),
-        x * (RHO - z) - y,
-        x * y - BETA * z,
-    ]
+impl OdeSystem<f64, 3> for Lorenz {
+    #[rustfmt::skip]
+    fn dynamics(&mut self, _time: f64, state: &[f64; 3]) -> [f64; 3] {
+        let [x, y, z] = state;
+        [
+            self.sigma * (y - x),
+            x * (self.rho - z) - y,
+            x * y - self.beta * z,
+        ]
+    }
 }
@@ -0,0 +1,66 @@
+use odesolvers::error::SolverError;
+use odesolvers::lyapunov::lyapunov_spectrum;
+use odesolvers::plot::Plot;
+use odesolvers::runge_kutta::Integrator;
+use odesolvers::system::OdeSystem;
+
+const PLOT_WIDTH: usize = 220;
+const PLOT_HEIGHT: usize = 70;
+
+fn main() -> Result<(), SolverError<f64>> {
+    let dt = 0.01;
+    let duration = 500.;
+    let lorenz = Lorenz { sigma: 10., rho: 28., beta: 8. / 3. };
+    let mut integrator = Integrator::build_system([1., 1., 1.], dt, lorenz);
+
+    // settle onto the attractor before measuring
+    integrator.solve_until(20.)?;
+    let spectrum = lyapunov_spectrum(&mut integrator, duration, 10)?;
+
+    let mut plot = Plot::build(PLOT_HEIGHT, PLOT_WIDTH);
+    plot.xbounds(-5., duration as f32).ybounds(-16., 3.).set_settings().subtick(true).subtick_spacing(1.);
+    plot.apply_settings();
+    for (idx, (red, green, blue)) in [(0, (255, 0, 0)), (1, (0, 160, 0)), (2, (0, 0, 255))] {
+        plot.set_brush().front_color(red, green, blue);
+        spectrum.history.windows(2).for_each(|window| {
+            let ((t0, start), (t1, end)) = (window[0], window[1]);
+            plot.plot_line(t0, start[idx], t1, end[idx]);
+        });
+    }
+    plot.display();
+    println!("running estimates of the three lyapunov exponents");
+
+    let [first, second, third] = spectrum.exponents;
+    println!("exponents {first:.4} {second:.4} {third:.4}, known values about 0.906 0 -14.572");
+    println!("sum {:.4}, the divergence gives {:.4}", first + second + third, -(10. + 1. + 8. / 3.));
+
+    Ok(())
+}
+
+struct Lorenz {
+    sigma: f64,
+    rho: f64,
+    beta: f64,
+}
+
+impl OdeSystem<f64, 3> for Lorenz {
+    #[rustfmt::skip]
+    fn dynamics(&mut self, _time: f64, state: &[f64; 3]) -> [f64; 3] {
+        let [x, y, z] = state;
+        [
+            self.sigma * (y - x),
+            x * (self.rho - z) - y,
+            x * y - self.beta * z,
+        ]
+    }
+
+    #[rustfmt::skip]
+    fn jacobian(&mut self, _time: f64, state: &[f64; 3]) -> [[f64; 3]; 3] {
+        let [x, y, z] = *state;
+        [
+            [-self.sigma, self.sigma, 0.],
+            [self.rho - z, -1., -x],
+            [y, x, -self.beta],
+        ]
+    }
+}
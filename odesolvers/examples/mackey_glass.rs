@@ -0,0 +1,40 @@
+use odesolvers::error::SolverError;
+use odesolvers::plot::Plot;
+use odesolvers::runge_kutta::DelayIntegrator;
+
+const PLOT_WIDTH: usize = 220;
+const PLOT_HEIGHT: usize = 70;
+
+fn main() -> Result<(), SolverError<f64>> {
+    let final_time = 500.;
+
+    // blood cell production reacts to the population one maturation delay ago
+    let mut integrator = DelayIntegrator::build([0.5], 1., &[TAU], |_time| [0.5], mackey_glass)?;
+    integrator.set_control().absolute_tolerance(1e-8).relative_tolerance(1e-6);
+    let output = integrator.solve_dynamic_with_time(final_time)?;
+
+    let mut plot = Plot::build(PLOT_HEIGHT, PLOT_WIDTH);
+    plot.xbounds(-5., final_time as f32).ybounds(0., 1.5).set_settings().subtick(true).subtick_spacing(25.);
+    plot.apply_settings();
+    plot.set_brush().front_color(0, 0, 255);
+    output.windows(2).for_each(|window| {
+        let ((t0, [start]), (t1, [end])) = (window[0], window[1]);
+        plot.plot_line(t0, start, t1, end);
+    });
+    plot.display();
+
+    println!("mackey glass with delay {TAU}, {} adaptive steps", output.len());
+    println!("value one delay before the end {:.6}", integrator.lookup(final_time - TAU)[0]);
+
+    Ok(())
+}
+
+const TAU: f64 = 17.;
+const BETA: f64 = 0.2;
+const GAMMA: f64 = 0.1;
+const EXPONENT: f64 = 10.;
+
+fn mackey_glass(state: &[f64; 1], delayed: &[[f64; 1]]) -> [f64; 1] {
+    let [lagged] = delayed[0];
+    [BETA * lagged / (1. + lagged.powf(EXPONENT)) - GAMMA * state[0]]
+}
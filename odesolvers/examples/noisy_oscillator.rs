@@ -0,0 +1,51 @@
+use odesolvers::error::SolverError;
+use odesolvers::plot::color_gradient;
+use odesolvers::plot::Plot;
+use odesolvers::stochastic::StochasticIntegrator;
+use odesolvers::stochastic::StochasticMethod;
+
+const PLOT_WIDTH: usize = 220;
+const PLOT_HEIGHT: usize = 70;
+
+fn main() -> Result<(), SolverError<f64>> {
+    let dt = 0.01;
+    let final_time = 20.;
+    let seed = 42;
+
+    // the same seed always gives the same paths, change it for a new ensemble
+    let mut integrator = StochasticIntegrator::build([1., 0.], dt, seed, oscillator_drift, thermal_noise);
+    integrator.set_method(StochasticMethod::Milstein);
+    let paths = integrator.sample_paths(final_time, 8)?;
+
+    let mut plot = Plot::build(PLOT_HEIGHT, PLOT_WIDTH);
+    plot.xbounds(-0.5, final_time as f32).ybounds(-2., 2.).set_settings().subtick(true).subtick_spacing(1.);
+    plot.apply_settings();
+    paths.iter().enumerate().for_each(|(idx, path)| {
+        let (red, green, blue) = color_gradient(idx as f32 * 4.);
+        plot.set_brush().front_color(red, green, blue);
+        path.windows(2).for_each(|window| {
+            let ((t0, [start, _]), (t1, [end, _])) = (window[0], window[1]);
+            plot.plot_line(t0, start, t1, end);
+        });
+    });
+    plot.display();
+
+    let mean = paths.iter().map(|path| path[path.len() - 1].1[0]).sum::<f64>() / paths.len() as f64;
+    println!("{} noisy damped oscillators, mean final position {mean:.4}", paths.len());
+
+    Ok(())
+}
+
+const OMEGA: f64 = 2.;
+const DAMPING: f64 = 0.2;
+const TEMPERATURE: f64 = 0.05;
+
+// only the velocity is kicked, the position follows it
+fn oscillator_drift(state: &[f64; 2]) -> [f64; 2] {
+    let [position, velocity] = *state;
+    [velocity, -OMEGA * OMEGA * position - DAMPING * velocity]
+}
+
+fn thermal_noise(_state: &[f64; 2]) -> [f64; 2] {
+    [0., (2. * DAMPING * TEMPERATURE).sqrt()]
+}
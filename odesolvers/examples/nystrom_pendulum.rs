@@ -0,0 +1,52 @@
+use std::f64::consts::PI;
+
+use odesolvers::error::SolverError;
+use odesolvers::nystrom::NystromIntegrator;
+use odesolvers::plot::Plot;
+
+const PLOT_WIDTH: usize = 220;
+const PLOT_HEIGHT: usize = 70;
+
+fn main() -> Result<(), SolverError<f64>> {
+    let final_time = 60.;
+    let (theta, theta_dot) = ([PI * 5. / 11.], [0.]);
+
+    // the damped pendulum written the way it reads, theta'' = f(theta, theta')
+    let mut integrator = NystromIntegrator::build(theta, theta_dot, 0.1, damped_pendulum);
+    integrator.set_control().absolute_tolerance(1e-10).relative_tolerance(1e-8);
+    let (times, angles, rates) = integrator.solve_trajectories(final_time)?;
+
+    let mut plot = Plot::build(PLOT_HEIGHT, PLOT_WIDTH);
+    plot.xbounds(-1., final_time as f32).ybounds(-1.6, 1.6).set_settings().subtick(true).subtick_spacing(5.);
+    plot.apply_settings();
+    plot_trajectory(&mut plot, &times, &angles, (0, 0, 255));
+    plot_trajectory(&mut plot, &times, &rates, (255, 0, 0));
+    plot.display();
+    println!("damped pendulum angle (blue) and angular velocity (red), {} adaptive steps", times.len());
+
+    // without damping the velocity stage drops out, three evaluations per fixed step instead of four
+    let mut conservative = NystromIntegrator::build_conservative(theta, theta_dot, 0.1, undamped_pendulum);
+    let ([angle], [rate]) = *conservative.solve_until(final_time)?.last().unwrap_or(&(theta, theta_dot));
+    println!("undamped pendulum at {final_time}: angle {angle:.6}, angular velocity {rate:.6}");
+
+    Ok(())
+}
+
+fn plot_trajectory(plot: &mut Plot, times: &[f64], values: &[[f64; 1]], (red, green, blue): (u8, u8, u8)) {
+    plot.set_brush().front_color(red, green, blue);
+    times.windows(2).zip(values.windows(2)).for_each(|(time, value)| {
+        plot.plot_line(time[0], value[0][0], time[1], value[1][0]);
+    });
+}
+
+const G: f64 = 9.8;
+const L: f64 = 10.;
+const C: f64 = 0.05;
+
+fn damped_pendulum(theta: &[f64; 1], theta_dot: &[f64; 1]) -> [f64; 1] {
+    [-theta[0].sin() * G / L - theta_dot[0] * C]
+}
+
+fn undamped_pendulum(theta: &[f64; 1]) -> [f64; 1] {
+    [-theta[0].sin() * G / L]
+}
@@ -0,0 +1,52 @@
+use odesolvers::error::SolverError;
+use odesolvers::plot::Plot;
+use odesolvers::runtime_sized::RuntimeIntegrator;
+
+const PLOT_WIDTH: usize = 220;
+const PLOT_HEIGHT: usize = 70;
+
+// the number of masses is only known once the program runs, e.g. `cargo run --example oscillator_chain 400`
+fn main() -> Result<(), SolverError<f64>> {
+    let masses: usize = std::env::args().nth(1).and_then(|arg| arg.parse().ok()).unwrap_or(200);
+    let dt = 0.01;
+    let final_time = 40.;
+
+    // a gaussian pulse in the displacements, everything at rest, velocities stored after the displacements
+    let mut initial_state = vec![0.; 2 * masses];
+    (0..masses).for_each(|idx| {
+        let offset = (idx as f64 - masses as f64 / 4.) / 5.;
+        initial_state[idx] = (-offset * offset).exp();
+    });
+
+    let mut integrator = RuntimeIntegrator::build(initial_state, dt, chain_dynamics);
+    integrator.set_control().absolute_tolerance(1e-8).relative_tolerance(1e-8);
+    integrator.solve_dynamic_until(final_time)?;
+
+    let mut plot = Plot::build(PLOT_HEIGHT, PLOT_WIDTH);
+    plot.xbounds(-1., masses as f32).ybounds(-1.2, 1.2).set_settings().subtick(true).subtick_spacing(10.);
+    plot.apply_settings();
+    plot.set_brush().front_color(0, 0, 255);
+    integrator.state()[..masses].windows(2).enumerate().for_each(|(idx, window)| {
+        plot.plot_line(idx as f64, window[0], (idx + 1) as f64, window[1]);
+    });
+    plot.display();
+    println!("pulse on a chain of {masses} masses at t = {}", integrator.curr_time());
+
+    Ok(())
+}
+
+const K: f64 = 25.;
+
+// masses joined by springs, both ends fixed to walls
+fn chain_dynamics(state: &[f64]) -> Vec<f64> {
+    let masses = state.len() / 2;
+    let (positions, velocities) = state.split_at(masses);
+    let position = |idx: isize| match idx {
+        idx if idx < 0 || idx >= masses as isize => 0.,
+        idx => positions[idx as usize],
+    };
+
+    let accelerations =
+        (0..masses as isize).map(|idx| K * (position(idx - 1) - 2. * position(idx) + position(idx + 1)));
+    velocities.iter().copied().chain(accelerations).collect()
+}
@@ -0,0 +1,65 @@
+use odesolvers::error::SolverError;
+use odesolvers::fitting::Fitter;
+use odesolvers::plot::Plot;
+use odesolvers::random::Random;
+
+const PLOT_WIDTH: usize = 220;
+const PLOT_HEIGHT: usize = 70;
+
+const NAMES: [&str; 3] = ["C", "K", "M"];
+
+fn main() -> Result<(), SolverError<f64>> {
+    let final_time = 20.;
+    let truth = [0.2, 3., 1.];
+    let mut fitter = Fitter::build_driven([0., 0.], 0.01, forced_oscillator_dynamics);
+
+    // a noisy position trace standing in for the measurements
+    let times: Vec<f64> = (1..=200).map(|idx| idx as f64 * final_time / 200.).collect();
+    let mut random = Random::build(7);
+    let measured: Vec<(f64, [f64; 1])> = fitter
+        .predict(truth, &times)?
+        .into_iter()
+        .map(|(time, [position, _])| (time, [position + 0.05 * random.normal()]))
+        .collect();
+
+    let fit = fitter.fit([0.5, 2., 1.5], &measured, |&[position, _]| [position])?;
+    let errors = fit.standard_errors().unwrap_or([f64::NAN; 3]);
+    (0..3).for_each(|idx| {
+        println!(
+            "{} = {:.4} +- {:.4}, true value {}",
+            NAMES[idx], fit.parameters[idx], errors[idx], truth[idx]
+        );
+    });
+    println!("{} iterations, sum of squares {:.4}", fit.iterations, fit.sum_of_squares);
+
+    let mut plot = Plot::build(PLOT_HEIGHT, PLOT_WIDTH);
+    plot.xbounds(-0.5, final_time as f32).ybounds(-4., 4.).set_settings().subtick(true).subtick_spacing(1.);
+    plot.apply_settings();
+    plot.set_brush().front_color(255, 0, 0);
+    measured.windows(2).for_each(|window| {
+        let ((t0, [start]), (t1, [end])) = (window[0], window[1]);
+        plot.plot_line(t0, start, t1, end);
+    });
+    plot.set_brush().front_color(0, 0, 255);
+    fitter.predict(fit.parameters, &times)?.windows(2).for_each(|window| {
+        let ((t0, [start, _]), (t1, [end, _])) = (window[0], window[1]);
+        plot.plot_line(t0, start, t1, end);
+    });
+    plot.display();
+    println!("measured position (red) and fitted model (blue)");
+
+    Ok(())
+}
+
+const F: f64 = 5.;
+const OMEGA: f64 = 1.2;
+
+#[rustfmt::skip]
+fn forced_oscillator_dynamics(time: f64, state: &[f64; 2], parameters: &[f64; 3]) -> [f64; 2] {
+    let [x, v] = *state;
+    let [c, k, m] = *parameters;
+    [
+        v,
+        -k / m * x + -c / m * v + F / m * (OMEGA * time).cos(),
+    ]
+}
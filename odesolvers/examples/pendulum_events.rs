@@ -0,0 +1,57 @@
+use std::f64::consts::PI;
+
+use odesolvers::error::SolverError;
+use odesolvers::event::Direction;
+use odesolvers::event::Event;
+use odesolvers::plot::Plot;
+use odesolvers::runge_kutta::Integrator;
+
+const PLOT_WIDTH: usize = 220;
+const PLOT_HEIGHT: usize = 70;
+
+fn main() -> Result<(), SolverError<f64>> {
+    let dt = 0.1;
+    let final_time = 60.;
+    let initial_state = [PI * 9. / 10., 0.];
+    let mut integrator = Integrator::build(initial_state, dt, pendulum_dynamics);
+    integrator.add_event(Event::build(|_, state: &[f64; 2]| state[0]).direction(Direction::Rising));
+
+    let output = integrator.solve_dynamic_with_time(final_time)?;
+
+    let mut plot = Plot::build(PLOT_HEIGHT, PLOT_WIDTH);
+    plot.xbounds(-2., 60.).ybounds(-4., 4.).set_settings().subtick(true).subtick_spacing(2.);
+    plot.apply_settings();
+
+    plot.set_brush().front_color(0, 0, 255);
+    output.windows(2).for_each(|window| {
+        let ((t0, start), (t1, end)) = (window[0], window[1]);
+        plot.plot_line(t0, start[0], t1, end[0]);
+    });
+
+    // mark every upward zero crossing of the angle
+    plot.set_brush().front_color(255, 0, 0);
+    integrator.event_log().iter().for_each(|record| {
+        plot.plot_line(record.time, -0.5, record.time, 0.5);
+    });
+    plot.display();
+
+    integrator.event_log().windows(2).for_each(|window| {
+        println!("period: {:.6}", window[1].time - window[0].time);
+    });
+    println!("pendulum upward zero crossings");
+
+    Ok(())
+}
+
+const G: f64 = 9.8;
+const L: f64 = 10.;
+const C: f64 = 0.02;
+
+#[rustfmt::skip]
+fn pendulum_dynamics(state: &[f64; 2]) -> [f64; 2] {
+    let [theta, theta_dot] = *state;
+    [
+        theta_dot,
+        -theta.sin() * G / L + -theta_dot * C,
+    ]
+}
@@ -0,0 +1,48 @@
+use odesolvers::error::SolverError;
+use odesolvers::plot::Plot;
+use odesolvers::shooting::Shooting;
+
+const PLOT_WIDTH: usize = 220;
+const PLOT_HEIGHT: usize = 70;
+
+fn main() -> Result<(), SolverError<f64>> {
+    let (target, flight_time) = (80., 5.);
+
+    // launched from the origin, it has to come down on the target once the flight time is up
+    let mut shooting = Shooting::build(0., flight_time, 0.01, projectile_dynamics);
+    let trajectory = shooting.solve([0., 0., 10., 10.], |launch, landing| {
+        [launch[0], launch[1], landing[0] - target, landing[1]]
+    })?;
+
+    let [_, _, vx, vy] = trajectory[0].1;
+    let angle = vy.atan2(vx).to_degrees();
+    let speed = (vx * vx + vy * vy).sqrt();
+    println!("launch at {angle:.2} degrees and {speed:.2} m/s, {} newton iterations", shooting.iterations());
+
+    let mut plot = Plot::build(PLOT_HEIGHT, PLOT_WIDTH);
+    plot.xbounds(-2., target as f32 + 5.).ybounds(-2., 50.).set_settings().subtick(true).subtick_spacing(5.);
+    plot.apply_settings();
+    plot.set_brush().front_color(0, 0, 255);
+    trajectory.windows(2).for_each(|window| {
+        let ((_, [x0, y0, ..]), (_, [x1, y1, ..])) = (window[0], window[1]);
+        plot.plot_line(x0, y0, x1, y1);
+    });
+    plot.display();
+
+    Ok(())
+}
+
+const G: f64 = 9.8;
+const DRAG: f64 = 0.01;
+
+#[rustfmt::skip]
+fn projectile_dynamics(state: &[f64; 4]) -> [f64; 4] {
+    let [_, _, vx, vy] = *state;
+    let speed = (vx * vx + vy * vy).sqrt();
+    [
+        vx,
+        vy,
+        -DRAG * speed * vx,
+        -G - DRAG * speed * vy,
+    ]
+}
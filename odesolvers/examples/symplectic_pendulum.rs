@@ -0,0 +1,85 @@
+use std::f64::consts::PI;
+
+use odesolvers::error::SolverError;
+use odesolvers::plot::Plot;
+use odesolvers::runge_kutta::Integrator;
+use odesolvers::symplectic::SymplecticIntegrator;
+use odesolvers::symplectic::SymplecticMethod;
+
+const PLOT_WIDTH: usize = 220;
+const PLOT_HEIGHT: usize = 70;
+
+fn main() -> Result<(), SolverError<f64>> {
+    let dt = 0.2;
+    let final_time = 10000.;
+    let (theta, theta_dot) = (PI * 3. / 4., 0.);
+    let initial_energy = energy(theta, theta_dot);
+
+    let mut plot = Plot::build(PLOT_HEIGHT, PLOT_WIDTH);
+    plot.xbounds(-200., 10000.).ybounds(-0.05, 0.02).set_settings().subtick(true).subtick_spacing(500.);
+    plot.apply_settings();
+
+    // rk4 loses energy step after step, the pendulum slowly winds down
+    let mut integrator = Integrator::build([theta, theta_dot], dt, pendulum_dynamics);
+    let output = integrator.solve_with_time(final_time)?;
+    let drift: Vec<(f64, f64)> =
+        output.iter().map(|&(time, [theta, theta_dot])| (time, relative_error(theta, theta_dot))).collect();
+    plot_drift(&mut plot, &drift, (255, 0, 0));
+    println!("rk4 final energy error {:e}", drift[drift.len() - 1].1);
+
+    let methods = [(SymplecticMethod::StormerVerlet, (0, 0, 255)), (SymplecticMethod::Yoshida6, (0, 200, 0))];
+    for (method, color) in methods {
+        let mut integrator = SymplecticIntegrator::build([theta], [theta_dot], dt, velocity, force);
+        integrator.set_method(method);
+        let output = integrator.solve_with_time(final_time)?;
+        let drift: Vec<(f64, f64)> = output
+            .iter()
+            .map(|&(time, ([theta], [theta_dot]))| (time, relative_error(theta, theta_dot)))
+            .collect();
+        plot_drift(&mut plot, &drift, color);
+
+        let worst = drift.iter().fold(0., |worst: f64, &(_, error)| worst.max(error.abs()));
+        println!("{method:?} worst energy error {worst:e}");
+    }
+    plot.display();
+    println!("relative energy error of rk4 (red), stormer verlet (blue) and yoshida 6 (green), initial energy {initial_energy:.3}");
+
+    Ok(())
+}
+
+fn plot_drift(plot: &mut Plot, drift: &[(f64, f64)], (red, green, blue): (u8, u8, u8)) {
+    plot.set_brush().front_color(red, green, blue);
+    drift.windows(2).for_each(|window| {
+        let ((t0, start), (t1, end)) = (window[0], window[1]);
+        plot.plot_line(t0, start, t1, end);
+    });
+}
+
+const G: f64 = 9.8;
+const L: f64 = 10.;
+
+fn energy(theta: f64, theta_dot: f64) -> f64 {
+    L * L * theta_dot * theta_dot / 2. - G * L * theta.cos()
+}
+
+fn relative_error(theta: f64, theta_dot: f64) -> f64 {
+    let initial = energy(PI * 3. / 4., 0.);
+    (energy(theta, theta_dot) - initial) / initial.abs()
+}
+
+fn velocity(momentum: &[f64; 1]) -> [f64; 1] {
+    *momentum
+}
+
+fn force(position: &[f64; 1]) -> [f64; 1] {
+    [-position[0].sin() * G / L]
+}
+
+#[rustfmt::skip]
+fn pendulum_dynamics(state: &[f64; 2]) -> [f64; 2] {
+    let [theta, theta_dot] = *state;
+    [
+        theta_dot,
+        -theta.sin() * G / L,
+    ]
+}
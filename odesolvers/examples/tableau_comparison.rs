@@ -0,0 +1,49 @@
+use odesolvers::butcher::ButcherTableau;
+use odesolvers::plot::Plot;
+use odesolvers::runge_kutta::Integrator;
+
+const PLOT_WIDTH: usize = 220;
+const PLOT_HEIGHT: usize = 70;
+
+fn main() {
+    let dt = 0.25;
+    let final_time = 40.;
+    let initial_state = [8., 0.];
+    let methods = [
+        (ButcherTableau::euler(), (255, 0, 0)),
+        (ButcherTableau::midpoint(), (0, 160, 0)),
+        (ButcherTableau::rk3(), (200, 0, 200)),
+        (ButcherTableau::rk4(), (0, 0, 255)),
+    ];
+
+    let mut plot = Plot::build(PLOT_HEIGHT, PLOT_WIDTH);
+    plot.xbounds(-2., 40.).ybounds(-10., 10.).set_settings().subtick(true).subtick_spacing(2.);
+    plot.apply_settings();
+
+    methods.iter().for_each(|(tableau, (red, green, blue))| {
+        let mut integrator = Integrator::build(initial_state, dt, oscillator_dynamics);
+        let mut prev = (integrator.curr_time(), integrator.state());
+        plot.set_brush().front_color(*red, *green, *blue);
+        while integrator.curr_time() < final_time {
+            let state = integrator.tableau_step(tableau);
+            plot.plot_line(prev.0, prev.1[0], integrator.curr_time(), state[0]);
+            prev = (integrator.curr_time(), state);
+        }
+        plot.display();
+        plot.clear_string();
+    });
+
+    println!("euler (red), midpoint (green), rk3 (purple) and rk4 (blue) on an undamped oscillator");
+}
+
+const K: f64 = 2.;
+const M: f64 = 1.;
+
+#[rustfmt::skip]
+fn oscillator_dynamics(state: &[f64; 2]) -> [f64; 2] {
+    let [x, v] = *state;
+    [
+        v,
+        -K / M * x,
+    ]
+}
@@ -0,0 +1,56 @@
+use std::f64::consts::PI;
+
+use odesolvers::error::SolverError;
+use odesolvers::plot::Plot;
+use odesolvers::runge_kutta::Integrator;
+
+const PLOT_WIDTH: usize = 220;
+const PLOT_HEIGHT: usize = 70;
+
+fn main() -> Result<(), SolverError<f64>> {
+    let dt = 0.1;
+    let final_time = 40.;
+    let initial_state = [PI * 3. / 4., 0.];
+    let mut integrator = Integrator::build(initial_state, dt, pendulum_dynamics);
+    integrator.set_control().absolute_tolerance(1e-10).relative_tolerance(1e-10);
+
+    // integrate forward, then back to the start from wherever the forward run ended
+    let forward = integrator.solve_dynamic_with_time(final_time)?;
+    let backward = integrator.solve_dynamic_with_time(0.)?;
+
+    let mut plot = Plot::build(PLOT_HEIGHT, PLOT_WIDTH);
+    plot.xbounds(-2., 40.).ybounds(-4., 4.).set_settings().subtick(true).subtick_spacing(2.);
+    plot.apply_settings();
+
+    plot.set_brush().front_color(0, 0, 255);
+    forward.windows(2).for_each(|window| {
+        let ((t0, start), (t1, end)) = (window[0], window[1]);
+        plot.plot_line(t0, start[0], t1, end[0]);
+    });
+    plot.set_brush().front_color(255, 0, 0);
+    backward.windows(2).for_each(|window| {
+        let ((t0, start), (t1, end)) = (window[0], window[1]);
+        plot.plot_line(t0, start[1], t1, end[1]);
+    });
+    plot.display();
+
+    let [theta, theta_dot] = integrator.state();
+    let (theta_error, theta_dot_error) = (theta - initial_state[0], theta_dot - initial_state[1]);
+    println!("pendulum integrated forward (angle, blue) and backward (velocity, red)");
+    let time = integrator.curr_time();
+    println!("returned to t = {time} off by {theta_error:e} in angle and {theta_dot_error:e} in velocity");
+
+    Ok(())
+}
+
+const G: f64 = 9.8;
+const L: f64 = 10.;
+
+#[rustfmt::skip]
+fn pendulum_dynamics(state: &[f64; 2]) -> [f64; 2] {
+    let [theta, theta_dot] = *state;
+    [
+        theta_dot,
+        -theta.sin() * G / L,
+    ]
+}
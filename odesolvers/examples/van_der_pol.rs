@@ -0,0 +1,54 @@
+use odesolvers::error::SolverError;
+use odesolvers::implicit::ImplicitIntegrator;
+use odesolvers::implicit::ImplicitMethod;
+use odesolvers::plot::Plot;
+
+const PLOT_WIDTH: usize = 220;
+const PLOT_HEIGHT: usize = 70;
+
+fn main() -> Result<(), SolverError<f64>> {
+    let dt = 1e-4;
+    let final_time = 3000.;
+    let initial_state = [2., 0.];
+
+    let mut plot = Plot::build(PLOT_HEIGHT, PLOT_WIDTH);
+    plot.xbounds(-100., 3000.).ybounds(-3., 3.).set_settings().subtick(true).subtick_spacing(100.);
+    plot.apply_settings();
+
+    let methods = [
+        (ImplicitMethod::TrBdf2, (0, 0, 255)),
+        (ImplicitMethod::Bdf, (255, 0, 0)),
+        (ImplicitMethod::Rodas4, (0, 200, 0)),
+        (ImplicitMethod::Auto, (200, 0, 200)),
+    ];
+    for (method, (red, green, blue)) in methods {
+        let mut integrator = ImplicitIntegrator::build(initial_state, dt, van_der_pol_dynamics);
+        integrator.set_method(method).set_control().absolute_tolerance(1e-6).relative_tolerance(1e-6);
+
+        let output = integrator.solve_with_time(final_time)?;
+
+        plot.set_brush().front_color(red, green, blue);
+        output.windows(2).for_each(|window| {
+            let ((t0, start), (t1, end)) = (window[0], window[1]);
+            plot.plot_line(t0, start[0], t1, end[0]);
+        });
+        println!("{method:?} took {} steps", output.len());
+    }
+    plot.display();
+    println!(
+        "stiff van der pol oscillator, tr-bdf2 (blue), bdf (red), rodas4 (green) and auto switching (purple)"
+    );
+
+    Ok(())
+}
+
+const MU: f64 = 1000.;
+
+#[rustfmt::skip]
+fn van_der_pol_dynamics(state: &[f64; 2]) -> [f64; 2] {
+    let [x, x_dot] = *state;
+    [
+        x_dot,
+        MU * (1. - x * x) * x_dot - x,
+    ]
+}
@@ -0,0 +1,237 @@
+use crate::scalar::Floating;
+
+#[derive(Clone, Debug)]
+pub struct ButcherTableau<Float> {
+    pub c: Vec<Float>,
+    pub a: Vec<Vec<Float>>,
+    pub b: Vec<Float>,
+    pub b_hat: Option<Vec<Float>>,
+    pub order: usize,
+}
+
+impl<Float> ButcherTableau<Float>
+where
+    Float: Floating,
+{
+    pub fn build(c: &[f64], a: &[&[f64]], b: &[f64], order: usize) -> Self {
+        ButcherTableau {
+            c: Self::floatify(c),
+            a: a.iter().map(|row| Self::floatify(row)).collect(),
+            b: Self::floatify(b),
+            b_hat: None,
+            order,
+        }
+    }
+
+    // embedded weights are expected to be one order below the main weights
+    pub fn embedded(mut self, b_hat: &[f64]) -> Self {
+        self.b_hat = Some(Self::floatify(b_hat));
+        self
+    }
+
+    pub fn stages(&self) -> usize {
+        self.b.len()
+    }
+
+    // first same as last, the final stage is evaluated at the solution and can start the next step
+    pub fn is_fsal(&self) -> bool {
+        let stages = self.stages();
+        stages > 0
+            && self.c[stages - 1] == Float::floatify(1.)
+            && self.a[stages - 1][..] == self.b[..stages - 1]
+    }
+
+    pub fn euler() -> Self {
+        Self::build(&[0.], &[&[]], &[1.], 1)
+    }
+
+    pub fn heun() -> Self {
+        Self::build(&[0., 1.], &[&[], &[1.]], &[1. / 2., 1. / 2.], 2).embedded(&[1., 0.])
+    }
+
+    pub fn midpoint() -> Self {
+        Self::build(&[0., 1. / 2.], &[&[], &[1. / 2.]], &[0., 1.], 2)
+    }
+
+    pub fn rk3() -> Self {
+        Self::build(&[0., 1. / 2., 1.], &[&[], &[1. / 2.], &[-1., 2.]], &[1. / 6., 2. / 3., 1. / 6.], 3)
+    }
+
+    pub fn rk4() -> Self {
+        Self::build(
+            &[0., 1. / 2., 1. / 2., 1.],
+            &[&[], &[1. / 2.], &[0., 1. / 2.], &[0., 0., 1.]],
+            &[1. / 6., 1. / 3., 1. / 3., 1. / 6.],
+            4,
+        )
+    }
+
+    pub fn three_eighths() -> Self {
+        Self::build(
+            &[0., 1. / 3., 2. / 3., 1.],
+            &[&[], &[1. / 3.], &[-1. / 3., 1.], &[1., -1., 1.]],
+            &[1. / 8., 3. / 8., 3. / 8., 1. / 8.],
+            4,
+        )
+    }
+
+    pub fn bogacki_shampine() -> Self {
+        Self::build(
+            &[0., 1. / 2., 3. / 4., 1.],
+            &[&[], &[1. / 2.], &[0., 3. / 4.], &[2. / 9., 1. / 3., 4. / 9.]],
+            &[2. / 9., 1. / 3., 4. / 9., 0.],
+            3,
+        )
+        .embedded(&[7. / 24., 1. / 4., 1. / 3., 1. / 8.])
+    }
+
+    pub fn cash_karp() -> Self {
+        Self::build(
+            &[0., 1. / 5., 3. / 10., 3. / 5., 1., 7. / 8.],
+            &[
+                &[],
+                &[1. / 5.],
+                &[3. / 40., 9. / 40.],
+                &[3. / 10., -9. / 10., 6. / 5.],
+                &[-11. / 54., 5. / 2., -70. / 27., 35. / 27.],
+                &[1631. / 55296., 175. / 512., 575. / 13824., 44275. / 110592., 253. / 4096.],
+            ],
+            &[37. / 378., 0., 250. / 621., 125. / 594., 0., 512. / 1771.],
+            5,
+        )
+        .embedded(&[2825. / 27648., 0., 18575. / 48384., 13525. / 55296., 277. / 14336., 1. / 4.])
+    }
+
+    pub fn dormand_prince() -> Self {
+        Self::build(
+            &[0., 1. / 5., 3. / 10., 4. / 5., 8. / 9., 1., 1.],
+            &[
+                &[],
+                &[1. / 5.],
+                &[3. / 40., 9. / 40.],
+                &[44. / 45., -56. / 15., 32. / 9.],
+                &[19372. / 6561., -25360. / 2187., 64448. / 6561., -212. / 729.],
+                &[9017. / 3168., -355. / 33., 46732. / 5247., 49. / 176., -5103. / 18656.],
+                &[35. / 384., 0., 500. / 1113., 125. / 192., -2187. / 6784., 11. / 84.],
+            ],
+            &[35. / 384., 0., 500. / 1113., 125. / 192., -2187. / 6784., 11. / 84., 0.],
+            5,
+        )
+        .embedded(&[
+            5179. / 57600.,
+            0.,
+            7571. / 16695.,
+            393. / 640.,
+            -92097. / 339200.,
+            187. / 2100.,
+            1. / 40.,
+        ])
+    }
+
+    fn floatify(values: &[f64]) -> Vec<Float> {
+        values.iter().map(|&value| Float::floatify(value)).collect()
+    }
+}
+
+// rosenbrock scheme in the transformed form, where each stage solves
+// (i / (h * gamma) - j) u_i = f(t + alpha_i h, y + sum a_ij u_j) + sum c_ij u_j / h + gammas_i h df/dt
+#[derive(Clone, Debug)]
+pub struct RosenbrockTableau<Float> {
+    pub gamma: Float,
+    pub alpha: Vec<Float>,
+    pub gammas: Vec<Float>,
+    pub a: Vec<Vec<Float>>,
+    pub c: Vec<Vec<Float>>,
+    pub m: Vec<Float>,
+    pub m_hat: Vec<Float>,
+    pub order: usize,
+}
+
+impl<Float> RosenbrockTableau<Float>
+where
+    Float: Floating,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn build(
+        gamma: f64,
+        alpha: &[f64],
+        gammas: &[f64],
+        a: &[&[f64]],
+        c: &[&[f64]],
+        m: &[f64],
+        m_hat: &[f64],
+        order: usize,
+    ) -> Self {
+        RosenbrockTableau {
+            gamma: Float::floatify(gamma),
+            alpha: ButcherTableau::floatify(alpha),
+            gammas: ButcherTableau::floatify(gammas),
+            a: a.iter().map(|row| ButcherTableau::floatify(row)).collect(),
+            c: c.iter().map(|row| ButcherTableau::floatify(row)).collect(),
+            m: ButcherTableau::floatify(m),
+            m_hat: ButcherTableau::floatify(m_hat),
+            order,
+        }
+    }
+
+    pub fn stages(&self) -> usize {
+        self.m.len()
+    }
+
+    // rang and angermann, third order with a second order embedded solution, unlike ros3p the two differ on
+    // linear problems so the error estimate never vanishes there
+    pub fn ros34pw2() -> Self {
+        let gamma = 0.435866521508459;
+        let last = [4.184760482319161, -0.285192017355496, 2.294280360279042];
+        Self::build(
+            gamma,
+            &[0., 0.871733043016918, 0.7315799577888524, 1.],
+            &[gamma, -gamma, -0.4133333762338865, 0.],
+            &[&[], &[2.], &[1.419217317455765, -0.259232211672970], &last],
+            &[
+                &[],
+                &[-4.588560720558085],
+                &[-4.184760482319161, 0.285192017355496],
+                &[-6.368179200128358, -6.795620944466837, 2.870098604331056],
+            ],
+            &[last[0], last[1], last[2], 1.],
+            &[3.907010534671193, 1.118047877820503, 0.521650232611491, 0.5],
+            3,
+        )
+    }
+
+    // hairer and wanner, stiffly accurate fourth order with a third order embedded solution
+    pub fn rodas4() -> Self {
+        let last = [1.221224509226641, 6.019134481288629, 12.53708332932087, -0.687886036105895];
+        Self::build(
+            0.25,
+            &[0., 0.386, 0.21, 0.63, 1., 1.],
+            &[0.25, -0.1043, 0.1035, -0.03620000000000023, 0., 0.],
+            &[
+                &[],
+                &[1.544],
+                &[0.9466785280815826, 0.2557011698983284],
+                &[3.314825187068521, 2.896124015972201, 0.9986419139977817],
+                &last,
+                &[last[0], last[1], last[2], last[3], 1.],
+            ],
+            &[
+                &[],
+                &[-5.6688],
+                &[-2.430093356833875, -0.2063599157091915],
+                &[-0.1073529058151375, -9.594562251023355, -20.47028614809616],
+                &[7.496443313967647, -10.24680431464352, -33.99990352819905, 11.7089089320616],
+                &[
+                    8.083246795921522,
+                    -7.981132988064893,
+                    -31.52159432874371,
+                    16.31930543123136,
+                    -6.058818238834054,
+                ],
+            ],
+            &[last[0], last[1], last[2], last[3], 1., 1.],
+            &[last[0], last[1], last[2], last[3], 1., 0.],
+            4,
+        )
+    }
+}
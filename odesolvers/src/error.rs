@@ -0,0 +1,64 @@
+use std::convert::Infallible;
+use std::fmt::Debug;
+use std::fmt::Display;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SolverError<Float> {
+    StepSizeUnderflow { time: Float, step: Float },
+    NonFiniteState { time: Float },
+    TooManySteps { time: Float, steps: usize },
+    NoConvergence { iterations: usize, residual: Float },
+    InvalidOutputTime { index: usize, time: Float },
+    DimensionMismatch { time: Float, expected: usize, found: usize },
+    BackwardIntegration { time: Float, final_time: Float },
+    InvalidDelay { delay: Float },
+    InvalidStepSize { step: Float },
+    InvalidDuration { duration: Float, step: Float },
+    DegenerateTangents { time: Float },
+}
+
+impl<Float> Display for SolverError<Float>
+where
+    Float: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolverError::StepSizeUnderflow { time, step } => {
+                write!(f, "step size {step} underflowed at time {time}")
+            }
+            SolverError::NonFiniteState { time } => write!(f, "state became non-finite at time {time}"),
+            SolverError::TooManySteps { time, steps } => {
+                write!(f, "gave up after {steps} steps at time {time}")
+            }
+            SolverError::NoConvergence { iterations, residual } => {
+                write!(f, "iteration stalled after {iterations} iterations with residual {residual}")
+            }
+            SolverError::InvalidOutputTime { index, time } => {
+                write!(f, "output time {time} at index {index} is out of order or behind the current time")
+            }
+            SolverError::DimensionMismatch { time, expected, found } => {
+                write!(f, "expected {expected} components but found {found} at time {time}")
+            }
+            SolverError::BackwardIntegration { time, final_time } => {
+                write!(f, "final time {final_time} lies behind the current time {time}")
+            }
+            SolverError::InvalidDelay { delay } => write!(f, "delay {delay} is not positive and finite"),
+            SolverError::InvalidStepSize { step } => write!(f, "step size {step} is zero or not finite"),
+            SolverError::InvalidDuration { duration, step } => {
+                write!(f, "duration {duration} does not span a single step of {step}")
+            }
+            SolverError::DegenerateTangents { time } => {
+                write!(f, "tangent vectors collapsed or overflowed at time {time}")
+            }
+        }
+    }
+}
+
+impl<Float> std::error::Error for SolverError<Float> where Float: Display + Debug {}
+
+// fixed size dynamics cannot fail, so their steppers convert into the same error as the runtime sized ones
+impl<Float> From<Infallible> for SolverError<Float> {
+    fn from(never: Infallible) -> Self {
+        match never {}
+    }
+}
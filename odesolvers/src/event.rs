@@ -0,0 +1,122 @@
+use crate::integration_shared::HermiteSegment;
+use crate::integration_shared::StepState;
+use crate::scalar::Floating;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Direction {
+    Rising,
+    Falling,
+    Both,
+}
+
+pub type EventFunction<Float, Slice> = Box<dyn FnMut(Float, &Slice) -> Float>;
+
+pub type Event<Float, const N: usize> = StateEvent<Float, [Float; N]>;
+
+pub type RuntimeEvent<Float> = StateEvent<Float, [Float]>;
+
+// slice is what the function sees of the state, an array for Integrator and a slice for RuntimeIntegrator
+pub struct StateEvent<Float, Slice: ?Sized> {
+    pub function: EventFunction<Float, Slice>,
+    pub direction: Direction,
+    pub terminal: bool,
+}
+
+impl<Float, Slice> StateEvent<Float, Slice>
+where
+    Float: Floating,
+    Slice: ?Sized,
+{
+    const TOLERANCE: f64 = 1e-12;
+    const MAX_ITERATIONS: usize = 100;
+
+    pub fn build(function: impl FnMut(Float, &Slice) -> Float + 'static) -> Self {
+        StateEvent { function: Box::new(function), direction: Direction::Both, terminal: false }
+    }
+
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    pub fn terminal(mut self, terminal: bool) -> Self {
+        self.terminal = terminal;
+        self
+    }
+
+    pub fn evaluate(&mut self, time: Float, state: &Slice) -> Float {
+        (self.function)(time, state)
+    }
+
+    pub fn crossed(&self, before: Float, after: Float) -> bool {
+        let zero = Float::default();
+        let rising = before < zero && after >= zero;
+        let falling = before > zero && after <= zero;
+        match self.direction {
+            Direction::Rising => rising,
+            Direction::Falling => falling,
+            Direction::Both => rising || falling,
+        }
+    }
+
+    // illinois variant of regula falsi, keeps the bracket and returns the time just past the crossing
+    pub fn locate<Vector>(
+        &mut self,
+        segment: &HermiteSegment<Float, Vector>,
+        before: Float,
+        after: Float,
+    ) -> Float
+    where
+        Vector: StepState<Float, Slice = Slice>,
+    {
+        let zero = Float::default();
+        let tolerance = (segment.t1 - segment.t0).abs() * Float::floatify(Self::TOLERANCE);
+        let (mut lower, mut upper) = (segment.t0, segment.t1);
+        let (mut glower, mut gupper) = (before, after);
+        if gupper == zero {
+            return upper;
+        }
+
+        let mut side = 0;
+        for _ in 0..Self::MAX_ITERATIONS {
+            if (upper - lower).abs() <= tolerance {
+                break;
+            }
+
+            let guess = (lower * gupper - upper * glower) / (gupper - glower);
+            let gguess = self.evaluate(guess, segment.interpolate(guess).slice());
+            if gguess == zero {
+                return guess;
+            }
+
+            if (gguess < zero) == (gupper < zero) {
+                upper = guess;
+                gupper = gguess;
+                if side == -1 {
+                    glower *= Float::floatify(0.5);
+                }
+                side = -1;
+            } else {
+                lower = guess;
+                glower = gguess;
+                if side == 1 {
+                    gupper *= Float::floatify(0.5);
+                }
+                side = 1;
+            }
+        }
+
+        upper
+    }
+}
+
+pub type EventRecord<Float, const N: usize> = StateEventRecord<Float, [Float; N]>;
+
+pub type RuntimeEventRecord<Float> = StateEventRecord<Float, Vec<Float>>;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct StateEventRecord<Float, Values> {
+    pub index: usize,
+    pub time: Float,
+    pub state: Values,
+}
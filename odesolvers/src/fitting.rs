@@ -0,0 +1,296 @@
+use crate::error::SolverError;
+use crate::linear_algebra::Matrix;
+use crate::runge_kutta::Integrator;
+use crate::scalar::Floating;
+use crate::system::Autonomous;
+use crate::system::OdeSystem;
+use crate::system::ParametricSystem;
+
+// parameters are picked up from here each time the integrator evaluates the dynamics
+struct Parameterised<Float, const P: usize, System> {
+    system: System,
+    parameters: [Float; P],
+}
+
+impl<Float, const N: usize, const P: usize, System> OdeSystem<Float, N> for Parameterised<Float, P, System>
+where
+    System: ParametricSystem<Float, N, P>,
+{
+    fn dynamics(&mut self, time: Float, state: &[Float; N]) -> [Float; N] {
+        self.system.dynamics(time, state, &self.parameters)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Fit<Float, const P: usize> {
+    pub parameters: [Float; P],
+    // observed minus predicted, observation by observation and component by component
+    pub residuals: Vec<Float>,
+    pub sum_of_squares: Float,
+    // residual variance times the inverse of J^T J, none when the parameters are not identifiable
+    pub covariance: Option<[[Float; P]; P]>,
+    pub iterations: usize,
+}
+
+impl<Float, const P: usize> Fit<Float, P>
+where
+    Float: Floating,
+{
+    // square roots of the covariance diagonal
+    pub fn standard_errors(&self) -> Option<[Float; P]> {
+        self.covariance.map(|covariance| {
+            let mut errors = [Float::default(); P];
+            (0..P).for_each(|idx| errors[idx] = covariance[idx][idx].abs().sqrt());
+            errors
+        })
+    }
+}
+
+// levenberg-marquardt least squares on the parameters of an ode, the initial state is held fixed
+pub struct Fitter<Float, const N: usize, const P: usize, System> {
+    integrator: Integrator<Float, N, Parameterised<Float, P, System>>,
+    start: Float,
+    initial: [Float; N],
+    tolerance: Float,
+    max_iterations: usize,
+}
+
+impl<Float, const N: usize, const P: usize, Dynamics> Fitter<Float, N, P, Autonomous<Dynamics>>
+where
+    Float: Floating,
+    Dynamics: FnMut(&[Float; N], &[Float; P]) -> [Float; N],
+{
+    pub fn build(state: [Float; N], delta_time: Float, dynamics: Dynamics) -> Self {
+        Self::build_system(state, delta_time, Autonomous(dynamics))
+    }
+}
+
+impl<Float, const N: usize, const P: usize, Dynamics> Fitter<Float, N, P, Dynamics>
+where
+    Float: Floating,
+    Dynamics: FnMut(Float, &[Float; N], &[Float; P]) -> [Float; N],
+{
+    pub fn build_driven(state: [Float; N], delta_time: Float, dynamics: Dynamics) -> Self {
+        Self::build_system(state, delta_time, dynamics)
+    }
+}
+
+impl<Float, const N: usize, const P: usize, System> Fitter<Float, N, P, System>
+where
+    Float: Floating,
+    System: ParametricSystem<Float, N, P>,
+{
+    const TOLERANCE_DEFAULT: f64 = 1e-10;
+    const MAX_ITERATIONS_DEFAULT: usize = 100;
+    const DAMPING_START: f64 = 1e-3;
+    const DAMPING_FACTOR: f64 = 10.;
+    const DAMPING_MAX: f64 = 1e12;
+
+    // fixed rk4 steps of delta_time that land on every observation, so the finite differences stay smooth
+    pub fn build_system(state: [Float; N], delta_time: Float, system: System) -> Self {
+        let parameterised = Parameterised { system, parameters: [Float::default(); P] };
+        Fitter {
+            integrator: Integrator::build_system(state, delta_time, parameterised),
+            start: Float::default(),
+            initial: state,
+            tolerance: Float::floatify(Self::TOLERANCE_DEFAULT),
+            max_iterations: Self::MAX_ITERATIONS_DEFAULT,
+        }
+    }
+
+    pub fn system(&mut self) -> &mut System {
+        &mut self.integrator.system().system
+    }
+
+    pub fn set_time(&mut self, time: Float) -> &mut Self {
+        self.start = time;
+        self
+    }
+
+    pub fn set_state(&mut self, state: [Float; N]) -> &mut Self {
+        self.initial = state;
+        self
+    }
+
+    // relative decrease of the sum of squares below which the fit counts as converged
+    pub fn set_tolerance(&mut self, tolerance: Float) -> &mut Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn set_max_iterations(&mut self, iterations: usize) -> &mut Self {
+        self.max_iterations = iterations;
+        self
+    }
+
+    // the model state at each time, integrated from the start with the given parameters
+    pub fn predict(
+        &mut self,
+        parameters: [Float; P],
+        times: &[Float],
+    ) -> Result<Vec<(Float, [Float; N])>, SolverError<Float>> {
+        self.integrator.system().parameters = parameters;
+        self.integrator.set_time(self.start).set_state(self.initial);
+        times
+            .iter()
+            .map(|&time| {
+                self.integrator.solve_until(time)?;
+                Ok((time, self.integrator.state()))
+            })
+            .collect()
+    }
+
+    // every state component is measured
+    pub fn fit_states(
+        &mut self,
+        guess: [Float; P],
+        data: &[(Float, [Float; N])],
+    ) -> Result<Fit<Float, P>, SolverError<Float>> {
+        self.fit(guess, data, |state| *state)
+    }
+
+    // observe maps a model state onto what was measured, such as a single position out of the full state
+    pub fn fit<const M: usize>(
+        &mut self,
+        guess: [Float; P],
+        data: &[(Float, [Float; M])],
+        mut observe: impl FnMut(&[Float; N]) -> [Float; M],
+    ) -> Result<Fit<Float, P>, SolverError<Float>> {
+        let times: Vec<Float> = data.iter().map(|&(time, _)| time).collect();
+        let mut residuals =
+            |fitter: &mut Self, parameters: [Float; P]| -> Result<Vec<Float>, SolverError<Float>> {
+                let predicted = fitter.predict(parameters, &times)?;
+                let mut residuals = Vec::with_capacity(data.len() * M);
+                data.iter().zip(&predicted).for_each(|((_, measured), (_, state))| {
+                    let modelled = observe(state);
+                    residuals.extend((0..M).map(|idx| measured[idx] - modelled[idx]));
+                });
+                Ok(residuals)
+            };
+
+        let mut parameters = guess;
+        let mut current = residuals(self, parameters)?;
+        let mut cost = sum_of_squares(&current);
+        let mut damping = Float::floatify(Self::DAMPING_START);
+        let mut iterations = 0;
+        let mut jacobian = self.jacobian(&parameters, &current, &mut residuals)?;
+
+        loop {
+            if iterations >= self.max_iterations {
+                return Err(SolverError::NoConvergence { iterations, residual: cost });
+            }
+            iterations += 1;
+
+            // (J^T J + damping diag(J^T J)) step = -J^T r, residuals are measured minus modelled
+            let normal = normal_matrix::<Float, P>(&jacobian);
+            let gradient = transpose_product::<Float, P>(&jacobian, &current);
+            let mut damped = Matrix::build(P, P);
+            (0..P).for_each(|row| {
+                (0..P).for_each(|col| damped[(row, col)] = normal[(row, col)]);
+                damped[(row, row)] += damping * normal[(row, row)].max(Float::epsilon());
+            });
+            let step = match damped.lu() {
+                Some(decomposition) => decomposition.solve(&gradient),
+                None => vec![Float::default(); P],
+            };
+
+            let mut trial = parameters;
+            (0..P).for_each(|idx| trial[idx] += step[idx]);
+            let attempt = residuals(self, trial).ok().filter(|trial| sum_of_squares(trial).is_finite());
+            match attempt {
+                Some(trial_residuals) if sum_of_squares(&trial_residuals) < cost => {
+                    let trial_cost = sum_of_squares(&trial_residuals);
+                    let decrease = (cost - trial_cost) / cost.max(Float::epsilon());
+                    (parameters, current, cost) = (trial, trial_residuals, trial_cost);
+                    damping = (damping / Float::floatify(Self::DAMPING_FACTOR)).max(Float::epsilon());
+                    // the covariance is taken from the jacobian, so it has to follow the parameters out of the loop
+                    jacobian = self.jacobian(&parameters, &current, &mut residuals)?;
+                    if decrease < self.tolerance {
+                        break;
+                    }
+                }
+                // no way downhill left, the current parameters are as good as the data allows
+                _ if damping > Float::floatify(Self::DAMPING_MAX) => break,
+                _ => damping *= Float::floatify(Self::DAMPING_FACTOR),
+            }
+        }
+
+        let covariance = covariance::<Float, P>(&jacobian, cost, current.len());
+        Ok(Fit { parameters, residuals: current, sum_of_squares: cost, covariance, iterations })
+    }
+
+    // one column per parameter, how the residuals change with it
+    fn jacobian(
+        &mut self,
+        parameters: &[Float; P],
+        current: &[Float],
+        residuals: &mut impl FnMut(&mut Self, [Float; P]) -> Result<Vec<Float>, SolverError<Float>>,
+    ) -> Result<Matrix<Float>, SolverError<Float>> {
+        let mut jacobian = Matrix::build(current.len(), P);
+        for col in 0..P {
+            let delta = Float::epsilon().sqrt() * parameters[col].abs().max(Float::floatify(1.));
+            let mut perturbed = *parameters;
+            perturbed[col] += delta;
+            let shifted = residuals(self, perturbed)?;
+            (0..current.len()).for_each(|row| jacobian[(row, col)] = (shifted[row] - current[row]) / delta);
+        }
+
+        Ok(jacobian)
+    }
+}
+
+fn sum_of_squares<Float>(values: &[Float]) -> Float
+where
+    Float: Floating,
+{
+    values.iter().fold(Float::default(), |sum, &value| sum + value * value)
+}
+
+fn normal_matrix<Float, const P: usize>(jacobian: &Matrix<Float>) -> Matrix<Float>
+where
+    Float: Floating,
+{
+    let mut normal = Matrix::build(P, P);
+    (0..P).for_each(|row| {
+        (0..P).for_each(|col| {
+            normal[(row, col)] = (0..jacobian.rows)
+                .fold(Float::default(), |sum, idx| sum + jacobian[(idx, row)] * jacobian[(idx, col)]);
+        })
+    });
+
+    normal
+}
+
+// the residuals decrease along -J^T r, and J is the derivative of the residuals, hence the sign
+fn transpose_product<Float, const P: usize>(jacobian: &Matrix<Float>, residuals: &[Float]) -> Vec<Float>
+where
+    Float: Floating,
+{
+    (0..P)
+        .map(|col| {
+            -(0..jacobian.rows).fold(Float::default(), |sum, idx| sum + jacobian[(idx, col)] * residuals[idx])
+        })
+        .collect()
+}
+
+fn covariance<Float, const P: usize>(
+    jacobian: &Matrix<Float>,
+    cost: Float,
+    observations: usize,
+) -> Option<[[Float; P]; P]>
+where
+    Float: Floating,
+{
+    let decomposition = normal_matrix::<Float, P>(jacobian).lu()?;
+    let variance = cost / Float::floatify(observations.saturating_sub(P).max(1) as f64);
+
+    let mut covariance = [[Float::default(); P]; P];
+    for col in 0..P {
+        let mut unit = [Float::default(); P];
+        unit[col] = Float::floatify(1.);
+        let column = decomposition.solve_array(&unit);
+        (0..P).for_each(|row| covariance[row][col] = column[row] * variance);
+    }
+
+    Some(covariance)
+}
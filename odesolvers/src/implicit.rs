@@ -0,0 +1,623 @@
+use std::ops::ControlFlow;
+
+use crate::butcher::ButcherTableau;
+use crate::butcher::RosenbrockTableau;
+use crate::error::SolverError;
+use crate::integration_shared::solve_clamped;
+use crate::integration_shared::ClampedSolve;
+use crate::integration_shared::Norm;
+use crate::integration_shared::State;
+use crate::integration_shared::StepControl;
+use crate::integration_shared::StepState;
+use crate::linear_algebra::LuDecomposition;
+use crate::linear_algebra::Matrix;
+use crate::scalar::Floating;
+use crate::system::Autonomous;
+use crate::system::OdeSystem;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ImplicitMethod {
+    BackwardEuler,
+    TrBdf2,
+    Bdf,
+    Ros34pw2,
+    Rodas4,
+    // dormand prince while the problem is non-stiff, rodas4 while it is stiff
+    Auto,
+}
+
+pub struct ImplicitIntegrator<Float, const N: usize, System> {
+    state: State<Float, N>,
+    dt: Float,
+    system: System,
+    time: Float,
+    control: StepControl<Float, N>,
+    method: ImplicitMethod,
+    history: Vec<(Float, State<Float, N>)>,
+    order: usize,
+    steps_at_order: usize,
+    rosenbrock: Option<RosenbrockTableau<Float>>,
+    explicit: Option<ButcherTableau<Float>>,
+    stiff: bool,
+    stiffness: Float,
+    switch_count: usize,
+    calm_count: usize,
+}
+
+impl<Float, const N: usize, Dynamics> ImplicitIntegrator<Float, N, Autonomous<Dynamics>>
+where
+    Float: Floating,
+    Dynamics: FnMut(&[Float; N]) -> [Float; N],
+{
+    pub fn build(state: [Float; N], delta_time: Float, dynamics: Dynamics) -> Self {
+        Self::build_system(state, delta_time, Autonomous(dynamics))
+    }
+}
+
+impl<Float, const N: usize, Dynamics> ImplicitIntegrator<Float, N, Dynamics>
+where
+    Float: Floating,
+    Dynamics: FnMut(Float, &[Float; N]) -> [Float; N],
+{
+    pub fn build_driven(state: [Float; N], delta_time: Float, dynamics: Dynamics) -> Self {
+        Self::build_system(state, delta_time, dynamics)
+    }
+}
+
+impl<Float, const N: usize, System> ImplicitIntegrator<Float, N, System>
+where
+    Float: Floating,
+    System: OdeSystem<Float, N>,
+{
+    const NEWTON_TOLERANCE: f64 = 1e-2;
+    const NEWTON_ITERATIONS: usize = 8;
+    const NEWTON_SHRINK: f64 = 0.25;
+    const MAX_ORDER: usize = 5;
+    const BDF_GROWTH: f64 = 2.;
+    // where the dormand prince stability region meets the negative real axis
+    const STABILITY_BOUNDARY: f64 = 3.25;
+    const SWITCH_STEPS: usize = 15;
+    const CALM_STEPS: usize = 6;
+
+    pub fn build_system(state: [Float; N], delta_time: Float, system: System) -> Self {
+        ImplicitIntegrator {
+            state: State::build(state),
+            dt: delta_time,
+            system,
+            time: Float::default(),
+            control: StepControl::build(),
+            method: ImplicitMethod::TrBdf2,
+            history: Vec::new(),
+            order: 1,
+            steps_at_order: 0,
+            rosenbrock: None,
+            explicit: None,
+            stiff: false,
+            stiffness: Float::default(),
+            switch_count: 0,
+            calm_count: 0,
+        }
+    }
+
+    pub const fn state(&self) -> [Float; N] {
+        self.state.values()
+    }
+
+    pub const fn delta_time(&self) -> Float {
+        self.dt
+    }
+
+    pub const fn curr_time(&self) -> Float {
+        self.time
+    }
+
+    pub const fn order(&self) -> usize {
+        self.order
+    }
+
+    pub const fn stiff(&self) -> bool {
+        self.stiff
+    }
+
+    pub fn system(&mut self) -> &mut System {
+        &mut self.system
+    }
+
+    pub fn set_control(&mut self) -> &mut StepControl<Float, N> {
+        &mut self.control
+    }
+
+    pub fn set_method(&mut self, method: ImplicitMethod) -> &mut Self {
+        self.method = method;
+        self.rosenbrock = match method {
+            ImplicitMethod::Ros34pw2 => Some(RosenbrockTableau::ros34pw2()),
+            ImplicitMethod::Rodas4 | ImplicitMethod::Auto => Some(RosenbrockTableau::rodas4()),
+            _ => None,
+        };
+        self.explicit = match method {
+            ImplicitMethod::Auto => Some(ButcherTableau::dormand_prince()),
+            _ => None,
+        };
+        self.restart();
+        self
+    }
+
+    pub fn set_time(&mut self, time: Float) -> &mut Self {
+        self.time = time;
+        self.restart();
+        self
+    }
+
+    pub fn set_state(&mut self, state: [Float; N]) -> &mut Self {
+        self.state = State::build(state);
+        self.restart();
+        self
+    }
+
+    pub fn step(&mut self) -> Result<[Float; N], SolverError<Float>> {
+        let derivative = self.evaluate(self.time, self.state);
+        if !derivative.is_finite() {
+            return Err(SolverError::NonFiniteState { time: self.time });
+        }
+        // the explicit half of the auto mode has no use for the jacobian
+        let explicit = self.method == ImplicitMethod::Auto && !self.stiff;
+        let jacobian = match explicit {
+            true => [[Float::default(); N]; N],
+            false => self.system.jacobian(self.time, &self.state.inner),
+        };
+
+        self.dt = self.control.limit_step(self.dt);
+        if self.method == ImplicitMethod::Bdf {
+            self.dt = self.dt.min(self.bdf_limit()).max(-self.bdf_limit());
+        }
+
+        let mut rejected = false;
+        loop {
+            if self.dt.abs() < self.control.min_step || self.time + self.dt == self.time {
+                return Err(SolverError::StepSizeUnderflow { time: self.time, step: self.dt });
+            }
+
+            let attempt = match self.method {
+                ImplicitMethod::BackwardEuler => self.backward_euler(&jacobian, derivative),
+                ImplicitMethod::TrBdf2 => self.trbdf2(&jacobian, derivative),
+                ImplicitMethod::Bdf => self.bdf(&jacobian, derivative),
+                ImplicitMethod::Auto if explicit => self.explicit(derivative),
+                ImplicitMethod::Ros34pw2 | ImplicitMethod::Rodas4 | ImplicitMethod::Auto => {
+                    self.rosenbrock(&jacobian, derivative)
+                }
+            };
+
+            // newton failing to converge says nothing about the error, so just retry with a much smaller step
+            let Some((solution, error, error_order)) = attempt else {
+                rejected = true;
+                self.dt *= Float::floatify(Self::NEWTON_SHRINK);
+                continue;
+            };
+
+            let (accepted, delta_time) = self.control.propose(self.dt, error, error_order, rejected);
+            if !accepted {
+                rejected = true;
+                self.dt = delta_time;
+                continue;
+            }
+
+            let taken = self.dt;
+            self.time += taken;
+            self.state = solution;
+            self.dt = delta_time;
+            if self.method == ImplicitMethod::Bdf {
+                self.history.push((self.time, self.state));
+                if self.history.len() > Self::MAX_ORDER + 2 {
+                    self.history.remove(0);
+                }
+                self.select_order(taken, rejected);
+            }
+            if self.method == ImplicitMethod::Auto {
+                let stiffness = match explicit {
+                    true => self.stiffness,
+                    false => taken.abs() * Matrix::from_rows(&jacobian).spectral_radius(),
+                };
+                self.switch_mode(stiffness);
+            }
+
+            return Ok(self.state());
+        }
+    }
+
+    pub fn solve_until(&mut self, final_time: Float) -> Result<Vec<[Float; N]>, SolverError<Float>> {
+        Ok(self.solve_with_time(final_time)?.into_iter().map(|(_, state)| state).collect())
+    }
+
+    pub fn solve_with_time(
+        &mut self,
+        final_time: Float,
+    ) -> Result<Vec<(Float, [Float; N])>, SolverError<Float>> {
+        solve_clamped(self, final_time, Self::step, |integrator, state| {
+            ControlFlow::Continue((integrator.time, state))
+        })
+    }
+
+    fn restart(&mut self) {
+        self.history.clear();
+        self.order = 1;
+        self.steps_at_order = 0;
+        self.stiff = false;
+        self.switch_count = 0;
+        self.calm_count = 0;
+    }
+
+    // counts steps pointing at the other mode, forgetting them after a short run of steps that do not
+    fn switch_mode(&mut self, stiffness: Float) {
+        let evidence = (stiffness > Float::floatify(Self::STABILITY_BOUNDARY)) != self.stiff;
+        if evidence {
+            self.switch_count += 1;
+            self.calm_count = 0;
+        } else {
+            self.calm_count += 1;
+            if self.calm_count >= Self::CALM_STEPS {
+                self.switch_count = 0;
+            }
+        }
+
+        if self.switch_count >= Self::SWITCH_STEPS {
+            self.stiff = !self.stiff;
+            self.switch_count = 0;
+            self.calm_count = 0;
+        }
+    }
+
+    fn evaluate(&mut self, time: Float, state: State<Float, N>) -> State<Float, N> {
+        State::build(self.system.dynamics(time, &state.inner))
+    }
+
+    // i - coefficient * jacobian, shared by every newton iteration of the step
+    fn iteration_matrix(jacobian: &[[Float; N]; N], coefficient: Float) -> Option<LuDecomposition<Float>> {
+        let mut matrix = Matrix::from_rows(jacobian);
+        matrix.data.iter_mut().for_each(|entry| *entry *= -coefficient);
+        (0..N).for_each(|idx| matrix[(idx, idx)] += Float::floatify(1.));
+        matrix.lu()
+    }
+
+    // simplified newton for y = base + coefficient * f(time, y)
+    fn newton(
+        &mut self,
+        time: Float,
+        base: State<Float, N>,
+        coefficient: Float,
+        guess: State<Float, N>,
+        matrix: &LuDecomposition<Float>,
+    ) -> Option<State<Float, N>> {
+        let scale = self.scale(&self.state);
+        let mut solution = guess;
+        let mut previous: Option<Float> = None;
+        for _ in 0..Self::NEWTON_ITERATIONS {
+            let derivative = self.evaluate(time, solution);
+            let residual = base + derivative * coefficient + solution * Float::floatify(-1.);
+            let correction = State::build(matrix.solve_array(&residual.inner));
+            solution = solution + correction;
+
+            let norm = correction.weighted_rms(&scale);
+            if !norm.is_finite() {
+                return None;
+            }
+            if norm <= Float::floatify(Self::NEWTON_TOLERANCE) {
+                return Some(solution);
+            }
+            // stop early when the iteration is diverging or converging too slowly to be useful
+            if previous.is_some_and(|previous| norm > previous * Float::floatify(0.9)) {
+                return None;
+            }
+            previous = Some(norm);
+        }
+
+        None
+    }
+
+    fn scale(&self, state: &State<Float, N>) -> State<Float, N> {
+        let mut scale = [Float::default(); N];
+        (0..N).for_each(|idx| {
+            scale[idx] = self.control.absolute[idx] + self.control.relative[idx] * state.inner[idx].abs();
+        });
+
+        State::build(scale)
+    }
+
+    fn error_norm(&self, error: State<Float, N>, solution: &State<Float, N>) -> Float {
+        self.control.error_norm(&error, &self.state, solution)
+    }
+
+    fn backward_euler(
+        &mut self,
+        jacobian: &[[Float; N]; N],
+        derivative: State<Float, N>,
+    ) -> Option<(State<Float, N>, Float, usize)> {
+        let matrix = Self::iteration_matrix(jacobian, self.dt)?;
+        let solution = self.newton(self.time + self.dt, self.state, self.dt, self.state, &matrix)?;
+
+        // half the gap to an explicit euler step, filtered through the iteration matrix to tame stiff components
+        let gap = solution + self.state * Float::floatify(-1.) + derivative * -self.dt;
+        let error = State::build(matrix.solve_array(&(gap * Float::floatify(0.5)).inner));
+
+        Some((solution, self.error_norm(error, &solution), 2))
+    }
+
+    fn trbdf2(
+        &mut self,
+        jacobian: &[[Float; N]; N],
+        derivative: State<Float, N>,
+    ) -> Option<(State<Float, N>, Float, usize)> {
+        let unity = Float::floatify(1.);
+        let gamma = Float::floatify(2. - 2f64.sqrt());
+        let coefficient = gamma * Float::floatify(0.5) * self.dt;
+        let matrix = Self::iteration_matrix(jacobian, coefficient)?;
+
+        // trapezoidal rule up to the intermediate point
+        let base = self.state + derivative * coefficient;
+        let intermediate =
+            self.newton(self.time + gamma * self.dt, base, coefficient, self.state, &matrix)?;
+        let intermediate_derivative = (intermediate + base * -unity) * (unity / coefficient);
+
+        // second order bdf through the start, the intermediate point and the end
+        let base = (intermediate * (unity / gamma)
+            + self.state * -((unity - gamma) * (unity - gamma) / gamma))
+            * (unity / (Float::floatify(2.) - gamma));
+        let solution = self.newton(self.time + self.dt, base, coefficient, intermediate, &matrix)?;
+        let final_derivative = (solution + base * -unity) * (unity / coefficient);
+
+        // third derivative from the divided difference of the three stage derivatives
+        let constant = (Float::floatify(-3.) * gamma * gamma + Float::floatify(4.) * gamma
+            - Float::floatify(2.))
+            / (Float::floatify(12.) * (Float::floatify(2.) - gamma));
+        let difference = (final_derivative + intermediate_derivative * -unity) * (unity / (unity - gamma))
+            + (intermediate_derivative + derivative * -unity) * -(unity / gamma);
+        let estimate = difference * (Float::floatify(2.) * constant * self.dt);
+        let error = State::build(matrix.solve_array(&estimate.inner));
+
+        Some((solution, self.error_norm(error, &solution), 3))
+    }
+
+    fn bdf(
+        &mut self,
+        jacobian: &[[Float; N]; N],
+        derivative: State<Float, N>,
+    ) -> Option<(State<Float, N>, Float, usize)> {
+        if self.history.is_empty() {
+            self.history.push((self.time, self.state));
+        }
+
+        let order = self.order.min(self.history.len());
+        let target = self.time + self.dt;
+        let points: Vec<Float> = std::iter::once(target)
+            .chain(self.history.iter().rev().take(order).map(|point| point.0))
+            .collect();
+
+        // derivative of the interpolating polynomial at the new point, split into new and past weights
+        let leading =
+            (1..=order).fold(Float::default(), |sum, idx| sum + Float::floatify(1.) / (target - points[idx]));
+        let past = (1..=order).fold(State::build([Float::default(); N]), |sum, idx| {
+            let numerator = (1..=order)
+                .filter(|&other| other != idx)
+                .fold(Float::floatify(1.), |product, other| product * (target - points[other]));
+            let denominator = (0..=order)
+                .filter(|&other| other != idx)
+                .fold(Float::floatify(1.), |product, other| product * (points[idx] - points[other]));
+            sum + self.history[self.history.len() - idx].1 * (numerator / denominator)
+        });
+
+        let coefficient = Float::floatify(1.) / leading;
+        let base = past * -coefficient;
+        let guess = self.extrapolate(target, order + 1);
+        let matrix = Self::iteration_matrix(jacobian, coefficient)?;
+        let solution = self.newton(target, base, coefficient, guess, &matrix)?;
+
+        let error = match self.history.len() > order {
+            true => self.bdf_estimate(order, self.dt, Some((target, solution)))?,
+            // a lone starting point has no history to difference, so compare against an explicit euler step
+            false => {
+                let gap = solution + self.state * Float::floatify(-1.) + derivative * -self.dt;
+                gap * Float::floatify(0.5)
+            }
+        };
+
+        Some((solution, self.error_norm(error, &solution), order + 1))
+    }
+
+    // explicit runge kutta step that also estimates h times the dominant eigenvalue from its last two stages
+    fn explicit(&mut self, derivative: State<Float, N>) -> Option<(State<Float, N>, Float, usize)> {
+        let tableau = self.explicit.as_ref()?;
+        let (time, dt, state) = (self.time, self.dt, self.state);
+
+        let mut stages: Vec<State<Float, N>> = Vec::with_capacity(tableau.stages());
+        let mut points: Vec<State<Float, N>> = Vec::with_capacity(tableau.stages());
+        for idx in 0..tableau.stages() {
+            let point = (0..idx).fold(state, |sum, prev| sum + stages[prev] * (dt * tableau.a[idx][prev]));
+            let slope = match idx {
+                0 => derivative,
+                _ => State::build(self.system.dynamics(time + tableau.c[idx] * dt, &point.inner)),
+            };
+            points.push(point);
+            stages.push(slope);
+        }
+
+        let weighted = |weights: &[Float]| {
+            (0..stages.len())
+                .fold(State::build([Float::default(); N]), |sum, idx| sum + stages[idx] * (dt * weights[idx]))
+        };
+        let solution = state + weighted(&tableau.b);
+        let weights: Vec<Float> =
+            tableau.b.iter().zip(tableau.b_hat.as_ref()?).map(|(&b, &b_hat)| b - b_hat).collect();
+        let error = weighted(&weights);
+
+        let last = stages.len() - 1;
+        let length = |values: State<Float, N>| {
+            values.inner.iter().fold(Float::default(), |sum, &value| sum + value * value).sqrt()
+        };
+        let spread = length(points[last] + points[last - 1] * Float::floatify(-1.));
+        let slope_spread = length(stages[last] + stages[last - 1] * Float::floatify(-1.));
+        self.stiffness = match spread > Float::default() {
+            true => dt.abs() * slope_spread / spread,
+            false => Float::default(),
+        };
+        let order = tableau.order;
+
+        Some((solution, self.error_norm(error, &solution), order))
+    }
+
+    // linearly implicit, so every stage is a single linear solve against the same factorisation
+    fn rosenbrock(
+        &mut self,
+        jacobian: &[[Float; N]; N],
+        derivative: State<Float, N>,
+    ) -> Option<(State<Float, N>, Float, usize)> {
+        let tableau = self.rosenbrock.as_ref()?;
+        let (time, dt, state) = (self.time, self.dt, self.state);
+        let coefficient = tableau.gamma * dt;
+        let matrix = Self::iteration_matrix(jacobian, coefficient)?;
+
+        let time_derivative = State::build(self.system.time_derivative(time, &state.inner));
+
+        let mut stages: Vec<State<Float, N>> = Vec::with_capacity(tableau.stages());
+        for idx in 0..tableau.stages() {
+            let stage = (0..idx).fold(state, |sum, prev| sum + stages[prev] * tableau.a[idx][prev]);
+            let slope = match idx {
+                0 => derivative,
+                _ => State::build(self.system.dynamics(time + tableau.alpha[idx] * dt, &stage.inner)),
+            };
+            let rhs = (0..idx).fold(slope + time_derivative * (tableau.gammas[idx] * dt), |sum, prev| {
+                sum + stages[prev] * (tableau.c[idx][prev] / dt)
+            });
+            stages.push(State::build(matrix.solve_array(&(rhs * coefficient).inner)));
+        }
+
+        let zero = State::build([Float::default(); N]);
+        let solution = (0..stages.len()).fold(state, |sum, idx| sum + stages[idx] * tableau.m[idx]);
+        let error = (0..stages.len())
+            .fold(zero, |sum, idx| sum + stages[idx] * (tableau.m[idx] - tableau.m_hat[idx]));
+        let order = tableau.order;
+
+        Some((solution, self.error_norm(error, &solution), order))
+    }
+
+    // lagrange extrapolation through the most recent history points
+    fn extrapolate(&self, time: Float, count: usize) -> State<Float, N> {
+        let points: Vec<&(Float, State<Float, N>)> = self.history.iter().rev().take(count).collect();
+        (0..points.len()).fold(State::build([Float::default(); N]), |sum, idx| {
+            let weight = (0..points.len())
+                .filter(|&other| other != idx)
+                .fold(Float::floatify(1.), |product, other| {
+                    product * (time - points[other].0) / (points[idx].0 - points[other].0)
+                });
+            sum + points[idx].1 * weight
+        })
+    }
+
+    // local error of an order q bdf step, from the q + 1 divided difference through the latest points
+    fn bdf_estimate(
+        &self,
+        order: usize,
+        step: Float,
+        newest: Option<(Float, State<Float, N>)>,
+    ) -> Option<State<Float, N>> {
+        let mut points: Vec<(Float, State<Float, N>)> = self.history.clone();
+        points.extend(newest);
+        if points.len() < order + 2 {
+            return None;
+        }
+        let points = &points[points.len() - order - 2..];
+
+        let mut table: Vec<State<Float, N>> = points.iter().map(|point| point.1).collect();
+        (1..points.len()).for_each(|level| {
+            (0..points.len() - level).for_each(|idx| {
+                let width = points[idx + level].0 - points[idx].0;
+                table[idx] =
+                    (table[idx + 1] + table[idx] * Float::floatify(-1.)) * (Float::floatify(1.) / width);
+            });
+        });
+
+        let harmonic = (1..=order).fold(0., |sum, idx| sum + 1. / idx as f64);
+        let factorial = (1..=order).fold(1., |product, idx| product * idx as f64);
+        let magnitude = step.abs().powf(Float::floatify((order + 1) as f64));
+        Some(table[0] * (Float::floatify(factorial / harmonic) * magnitude))
+    }
+
+    // compares the error estimates of the neighbouring orders and moves to whichever allows the biggest step
+    fn select_order(&mut self, taken: Float, rejected: bool) {
+        self.steps_at_order += 1;
+        if rejected || self.steps_at_order <= self.order {
+            return;
+        }
+
+        let ratio = |integrator: &Self, order: usize, bias: f64| -> Option<Float> {
+            let estimate = integrator.bdf_estimate(order, taken, None)?;
+            let error = integrator.control.error_norm(&estimate, &integrator.state, &integrator.state);
+            let exponent = Float::floatify(1. / (order + 1) as f64);
+            Some((Float::floatify(1.) / error.max(Float::epsilon())).powf(exponent) / Float::floatify(bias))
+        };
+
+        let current = ratio(self, self.order, 1.2);
+        let lower = if self.order > 1 { ratio(self, self.order - 1, 1.3) } else { None };
+        let higher = if self.order < Self::MAX_ORDER { ratio(self, self.order + 1, 1.4) } else { None };
+
+        let mut best = (self.order, current.unwrap_or(Float::default()));
+        if let Some(lower) = lower
+            && lower > best.1
+        {
+            best = (self.order - 1, lower);
+        }
+        if let Some(higher) = higher
+            && higher > best.1
+        {
+            best = (self.order + 1, higher);
+        }
+
+        if best.0 != self.order && best.1 > Float::default() {
+            self.order = best.0;
+            self.steps_at_order = 0;
+            let growth = best.1.min(self.control.growth_limit).min(Float::floatify(Self::BDF_GROWTH));
+            self.dt = self.control.limit_step(taken * growth);
+        }
+    }
+
+    // variable step bdf loses stability on sudden step changes, so growth is held back
+    fn bdf_limit(&self) -> Float {
+        match self.history.last() {
+            Some(_) if self.history.len() > 1 => {
+                let last = self.history[self.history.len() - 1].0 - self.history[self.history.len() - 2].0;
+                last.abs() * Float::floatify(Self::BDF_GROWTH)
+            }
+            _ => self.dt.abs(),
+        }
+    }
+}
+
+impl<Float, const N: usize, System> ClampedSolve<Float> for ImplicitIntegrator<Float, N, System>
+where
+    Float: Floating,
+{
+    fn time(&mut self) -> &mut Float {
+        &mut self.time
+    }
+
+    fn step_size(&mut self) -> &mut Float {
+        &mut self.dt
+    }
+
+    fn max_steps(&self) -> usize {
+        self.control.max_steps
+    }
+
+    fn min_step(&mut self) -> &mut Float {
+        &mut self.control.min_step
+    }
+
+    fn is_finite(&self) -> bool {
+        self.state.is_finite()
+    }
+
+    // the multistep history ends where the integrator is
+    fn landed(&mut self, _landing: Float, final_time: Float) {
+        if let Some(last) = self.history.last_mut() {
+            last.0 = final_time;
+        }
+    }
+}
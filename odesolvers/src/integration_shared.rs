@@ -1,15 +1,654 @@
+use std::convert::Infallible;
 use std::ops::Add;
-use std::ops::AddAssign;
+use std::ops::ControlFlow;
 use std::ops::Mul;
 
-pub type DynamicsFunction<Float, const N: usize> = fn(&[Float; N]) -> [Float; N];
+use crate::butcher::ButcherTableau;
+use crate::error::SolverError;
+use crate::scalar::Floating;
+use crate::system::OdeSystem;
+use crate::system::RuntimeSystem;
 
-pub trait IntegrationStep<State> {
-    fn runge_kutta_4(&self) -> State;
+pub struct EmbeddedStep<State> {
+    pub solution: State,
+    pub error: State,
+    pub derivative: State,
 }
 
 pub trait Norm<Float> {
-    fn norm(&self) -> Float;
+    fn weighted_rms(&self, scale: &Self) -> Float;
+}
+
+// the types that go with a state, kept apart from StepState so naming them puts no bounds on the float
+pub trait StateTypes<Float> {
+    // what callers hand in and get back, an array for fixed size states and a vector for runtime sized ones
+    type Values;
+
+    // what event functions are shown of the state
+    type Slice: ?Sized;
+
+    type Tolerance;
+}
+
+// what the shared steppers need from a state, whether its size is fixed or chosen at runtime
+pub trait StepState<Float>: StateTypes<Float, Values: Clone, Tolerance: Tolerances<Float>> + Clone {
+    fn from_values(values: Self::Values) -> Self;
+
+    fn values(&self) -> Self::Values;
+
+    fn slice(&self) -> &Self::Slice;
+
+    // default tolerances, one per component
+    fn default_control(&self) -> Control<Float, Self::Tolerance>
+    where
+        Float: Floating;
+
+    // base plus every term times its weight
+    fn combine(base: &Self, terms: &[(&Self, Float)]) -> Self;
+
+    fn zeroed(&self) -> Self;
+
+    fn components(&self) -> &[Float];
+
+    fn is_finite(&self) -> bool
+    where
+        Float: Floating,
+    {
+        self.components().iter().all(|value| value.is_finite())
+    }
+}
+
+// evaluates the dynamics on a state, fixed size states take an OdeSystem and runtime sized ones a RuntimeSystem
+pub trait Evaluate<Float, System>: StepState<Float> {
+    // what evaluating the dynamics can fail with, nothing for fixed size states
+    type Failure;
+
+    fn dynamics(system: &mut System, time: Float, state: &Self) -> Result<Self, Self::Failure>;
+}
+
+// per component tolerances, an array for fixed size states and a vector for runtime sized ones
+pub trait Tolerances<Float>: Clone {
+    fn fill(&mut self, tolerance: Float);
+
+    fn component(&self, idx: usize) -> Float;
+
+    fn dimension(&self) -> usize;
+}
+
+impl<Float, const N: usize> Tolerances<Float> for [Float; N]
+where
+    Float: Copy,
+{
+    fn fill(&mut self, tolerance: Float) {
+        *self = [tolerance; N];
+    }
+
+    fn component(&self, idx: usize) -> Float {
+        self[idx]
+    }
+
+    fn dimension(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<Float> Tolerances<Float> for Vec<Float>
+where
+    Float: Copy,
+{
+    fn fill(&mut self, tolerance: Float) {
+        self.iter_mut().for_each(|value| *value = tolerance);
+    }
+
+    fn component(&self, idx: usize) -> Float {
+        self[idx]
+    }
+
+    fn dimension(&self) -> usize {
+        self.len()
+    }
+}
+
+pub type StepControl<Float, const N: usize> = Control<Float, [Float; N]>;
+
+pub type RuntimeStepControl<Float> = Control<Float, Vec<Float>>;
+
+#[derive(Clone, Copy)]
+pub struct Control<Float, Tolerance> {
+    pub absolute: Tolerance,
+    pub relative: Tolerance,
+
+    pub min_step: Float,
+    pub max_step: Float,
+    pub max_steps: usize,
+
+    pub growth_limit: Float,
+    pub shrink_limit: Float,
+}
+
+impl<Float, const N: usize> Control<Float, [Float; N]>
+where
+    Float: Floating,
+{
+    pub fn build() -> Self {
+        Self::build_tolerances(
+            [Float::floatify(Self::ABSOLUTE_DEFAULT); N],
+            [Float::floatify(Self::RELATIVE_DEFAULT); N],
+        )
+    }
+}
+
+impl<Float> Control<Float, Vec<Float>>
+where
+    Float: Floating,
+{
+    pub fn build(dimension: usize) -> Self {
+        Self::build_tolerances(
+            vec![Float::floatify(Self::ABSOLUTE_DEFAULT); dimension],
+            vec![Float::floatify(Self::RELATIVE_DEFAULT); dimension],
+        )
+    }
+
+    // new components take the last tolerance of each kind
+    pub fn resize(&mut self, dimension: usize) -> &mut Self {
+        let absolute = self.absolute.last().copied().unwrap_or(Float::floatify(Self::ABSOLUTE_DEFAULT));
+        let relative = self.relative.last().copied().unwrap_or(Float::floatify(Self::RELATIVE_DEFAULT));
+        self.absolute.resize(dimension, absolute);
+        self.relative.resize(dimension, relative);
+        self
+    }
+}
+
+impl<Float, Tolerance> Control<Float, Tolerance>
+where
+    Float: Floating,
+    Tolerance: Tolerances<Float>,
+{
+    const ABSOLUTE_DEFAULT: f64 = 1e-8;
+    const RELATIVE_DEFAULT: f64 = 1e-6;
+    const MAX_STEPS_DEFAULT: usize = 1_000_000;
+    const GROWTH_DEFAULT: f64 = 5.;
+    const SHRINK_DEFAULT: f64 = 0.2;
+    const SAFETY: f64 = 0.9;
+
+    fn build_tolerances(absolute: Tolerance, relative: Tolerance) -> Self {
+        Control {
+            absolute,
+            relative,
+
+            min_step: Float::default(),
+            max_step: Float::floatify(f64::INFINITY),
+            max_steps: Self::MAX_STEPS_DEFAULT,
+
+            growth_limit: Float::floatify(Self::GROWTH_DEFAULT),
+            shrink_limit: Float::floatify(Self::SHRINK_DEFAULT),
+        }
+    }
+
+    pub fn absolute_tolerance(&mut self, tolerance: Float) -> &mut Self {
+        self.absolute.fill(tolerance);
+        self
+    }
+
+    pub fn relative_tolerance(&mut self, tolerance: Float) -> &mut Self {
+        self.relative.fill(tolerance);
+        self
+    }
+
+    pub fn absolute_tolerances(&mut self, tolerances: Tolerance) -> &mut Self {
+        self.absolute = tolerances;
+        self
+    }
+
+    pub fn relative_tolerances(&mut self, tolerances: Tolerance) -> &mut Self {
+        self.relative = tolerances;
+        self
+    }
+
+    pub fn min_step(&mut self, step: Float) -> &mut Self {
+        self.min_step = step;
+        self
+    }
+
+    pub fn max_step(&mut self, step: Float) -> &mut Self {
+        self.max_step = step;
+        self
+    }
+
+    pub fn max_steps(&mut self, steps: usize) -> &mut Self {
+        self.max_steps = steps;
+        self
+    }
+
+    pub fn growth_limit(&mut self, factor: Float) -> &mut Self {
+        self.growth_limit = factor;
+        self
+    }
+
+    pub fn shrink_limit(&mut self, factor: Float) -> &mut Self {
+        self.shrink_limit = factor;
+        self
+    }
+
+    // error_order is the power of the step the error estimate scales with, the accepted flag comes first
+    pub fn propose(&self, step: Float, error: Float, error_order: usize, rejected: bool) -> (bool, Float) {
+        let unity = Float::floatify(1.);
+        if !error.is_finite() {
+            return (false, step * self.shrink_limit);
+        }
+
+        let factor = match error == Float::default() {
+            true => self.growth_limit,
+            false => {
+                Float::floatify(Self::SAFETY) * (unity / error).powf(Float::floatify(1. / error_order as f64))
+            }
+        };
+
+        if error <= unity {
+            let growth = if rejected { unity } else { self.growth_limit };
+            return (true, self.limit_step(step * factor.min(growth)));
+        }
+
+        (false, step * factor.max(self.shrink_limit).min(unity))
+    }
+
+    // clamps the magnitude only, so steps keep pointing in the direction of integration
+    pub fn limit_step(&self, step: Float) -> Float {
+        match step.abs() > self.max_step {
+            true => self.max_step * step.signum(),
+            false => step,
+        }
+    }
+
+    // scaled like ode45, so a norm at or below one means the step met every tolerance
+    pub fn error_norm<Vector>(&self, error: &Vector, prev: &Vector, next: &Vector) -> Float
+    where
+        Vector: StepState<Float>,
+    {
+        let (error, prev, next) = (error.components(), prev.components(), next.components());
+        let sum = (0..error.len()).fold(Float::default(), |sum, idx| {
+            let magnitude = prev[idx].abs().max(next[idx].abs());
+            let scale = self.absolute.component(idx) + self.relative.component(idx) * magnitude;
+            let scaled = error[idx] / scale;
+            sum + scaled * scaled
+        });
+
+        (sum / Float::floatify(error.len().max(1) as f64)).sqrt()
+    }
+}
+
+// the accept or reject loop behind every explicit adaptive stepper, whatever its state looks like
+pub trait AdaptiveStep<Float, Tolerance>: ClampedSolve<Float>
+where
+    Float: Floating,
+    Tolerance: Tolerances<Float>,
+{
+    fn control(&self) -> &Control<Float, Tolerance>;
+
+    // attempt returns a candidate and its scaled error norm, accept moves the integrator onto the candidate
+    fn adaptive_loop<Candidate>(
+        &mut self,
+        error_order: usize,
+        mut attempt: impl FnMut(&mut Self) -> Result<(Candidate, Float), SolverError<Float>>,
+        mut accept: impl FnMut(&mut Self, Candidate),
+    ) -> Result<(), SolverError<Float>> {
+        let step = *self.step_size();
+        let limited = self.control().limit_step(step);
+        *self.step_size() = limited;
+
+        let mut rejected = false;
+        loop {
+            let (time, step) = (*self.time(), *self.step_size());
+            if step.abs() < self.control().min_step || time + step == time {
+                return Err(SolverError::StepSizeUnderflow { time, step });
+            }
+
+            let (candidate, error) = attempt(self)?;
+            let (accepted, delta_time) = self.control().propose(step, error, error_order, rejected);
+
+            if accepted {
+                *self.time() += step;
+                accept(self, candidate);
+                *self.step_size() = delta_time;
+
+                return Ok(());
+            }
+
+            rejected = true;
+            *self.step_size() = delta_time;
+        }
+    }
+
+    // a tableau without embedded weights has no error estimate, so its step is taken at the stored size
+    fn tableau_loop<Candidate>(
+        &mut self,
+        tableau: &ButcherTableau<Float>,
+        mut attempt: impl FnMut(&mut Self) -> Result<(Candidate, Float), SolverError<Float>>,
+        mut accept: impl FnMut(&mut Self, Candidate),
+    ) -> Result<(), SolverError<Float>> {
+        if tableau.b_hat.is_some() {
+            return self.adaptive_loop(tableau.order, attempt, accept);
+        }
+
+        let (candidate, _) = attempt(self)?;
+        let step = *self.step_size();
+        *self.time() += step;
+        accept(self, candidate);
+
+        Ok(())
+    }
+}
+
+// the explicit steppers, written once for every state type
+pub trait IntegrationStep<Float, Vector>: AdaptiveStep<Float, Vector::Tolerance>
+where
+    Float: Floating,
+    Vector: StepState<Float>,
+{
+    // what evaluating the dynamics can fail with, nothing for fixed size states
+    type Failure;
+
+    fn current(&self) -> &Vector;
+
+    // the dynamics at the current time plus offset
+    fn slope(&mut self, offset: Float, state: &Vector) -> Result<Vector, Self::Failure>;
+
+    // moves onto an accepted solution, the derivative there comes along when the method already has it
+    fn accept(&mut self, solution: Vector, derivative: Option<Vector>);
+
+    fn runge_kutta_4(&mut self) -> Result<Vector, Self::Failure> {
+        let (dt, state) = (*self.step_size(), self.current().clone());
+        let half = dt / Float::floatify(2.);
+        let k1 = self.slope(Float::default(), &state)?;
+        let k2 = self.slope(half, &Vector::combine(&state, &[(&k1, half)]))?;
+        let k3 = self.slope(half, &Vector::combine(&state, &[(&k2, half)]))?;
+        let k4 = self.slope(dt, &Vector::combine(&state, &[(&k3, dt)]))?;
+
+        let one = Float::floatify(1.);
+        let (outer, inner) = (Vector::combine(&k1, &[(&k4, one)]), Vector::combine(&k2, &[(&k3, one)]));
+        Ok(Vector::combine(&state, &[(&outer, dt / Float::floatify(6.)), (&inner, dt / Float::floatify(3.))]))
+    }
+
+    fn dormand_prince_45(&mut self, derivative: Vector) -> Result<EmbeddedStep<Vector>, Self::Failure> {
+        let (dt, state) = (*self.step_size(), self.current().clone());
+        let coeff = |value: f64| dt * Float::floatify(value);
+
+        let k1 = derivative;
+        let k2 = self.slope(coeff(1. / 5.), &Vector::combine(&state, &[(&k1, coeff(1. / 5.))]))?;
+        let k3 = self.slope(
+            coeff(3. / 10.),
+            &Vector::combine(&state, &[(&k1, coeff(3. / 40.)), (&k2, coeff(9. / 40.))]),
+        )?;
+        let k4 = self.slope(
+            coeff(4. / 5.),
+            &Vector::combine(
+                &state,
+                &[(&k1, coeff(44. / 45.)), (&k2, coeff(-56. / 15.)), (&k3, coeff(32. / 9.))],
+            ),
+        )?;
+        let k5 = self.slope(
+            coeff(8. / 9.),
+            &Vector::combine(
+                &state,
+                &[
+                    (&k1, coeff(19372. / 6561.)),
+                    (&k2, coeff(-25360. / 2187.)),
+                    (&k3, coeff(64448. / 6561.)),
+                    (&k4, coeff(-212. / 729.)),
+                ],
+            ),
+        )?;
+        let k6 = self.slope(
+            dt,
+            &Vector::combine(
+                &state,
+                &[
+                    (&k1, coeff(9017. / 3168.)),
+                    (&k2, coeff(-355. / 33.)),
+                    (&k3, coeff(46732. / 5247.)),
+                    (&k4, coeff(49. / 176.)),
+                    (&k5, coeff(-5103. / 18656.)),
+                ],
+            ),
+        )?;
+
+        let solution = Vector::combine(
+            &state,
+            &[
+                (&k1, coeff(35. / 384.)),
+                (&k3, coeff(500. / 1113.)),
+                (&k4, coeff(125. / 192.)),
+                (&k5, coeff(-2187. / 6784.)),
+                (&k6, coeff(11. / 84.)),
+            ],
+        );
+        let k7 = self.slope(dt, &solution)?;
+
+        // difference between the fifth and embedded fourth order weights
+        let error = Vector::combine(
+            &state.zeroed(),
+            &[
+                (&k1, coeff(71. / 57600.)),
+                (&k3, coeff(-71. / 16695.)),
+                (&k4, coeff(71. / 1920.)),
+                (&k5, coeff(-17253. / 339200.)),
+                (&k6, coeff(22. / 525.)),
+                (&k7, coeff(-1. / 40.)),
+            ],
+        );
+
+        Ok(EmbeddedStep { solution, error, derivative: k7 })
+    }
+
+    fn explicit_tableau(
+        &mut self,
+        tableau: &ButcherTableau<Float>,
+    ) -> Result<(Vector, Option<Vector>), Self::Failure> {
+        let (dt, state) = (*self.step_size(), self.current().clone());
+        let mut stages: Vec<Vector> = Vec::with_capacity(tableau.stages());
+        for stage in 0..tableau.stages() {
+            let terms: Vec<(&Vector, Float)> =
+                (0..stage).map(|prev| (&stages[prev], dt * tableau.a[stage][prev])).collect();
+            let slope = self.slope(dt * tableau.c[stage], &Vector::combine(&state, &terms))?;
+            stages.push(slope);
+        }
+
+        let weighted = |base: &Vector, weights: &[Float]| {
+            let terms: Vec<(&Vector, Float)> =
+                stages.iter().zip(weights).map(|(stage, &weight)| (stage, dt * weight)).collect();
+            Vector::combine(base, &terms)
+        };
+        let solution = weighted(&state, &tableau.b);
+        let error = tableau.b_hat.as_ref().map(|b_hat| {
+            let weights: Vec<Float> = tableau.b.iter().zip(b_hat).map(|(&b, &b_hat)| b - b_hat).collect();
+            weighted(&state.zeroed(), &weights)
+        });
+
+        Ok((solution, error))
+    }
+
+    // attempt returns the solution, its error estimate and optionally the derivative at the solution
+    fn adaptive_step(
+        &mut self,
+        error_order: usize,
+        mut attempt: impl FnMut(&mut Self) -> Result<(Vector, Vector, Option<Vector>), SolverError<Float>>,
+    ) -> Result<(), SolverError<Float>> {
+        self.adaptive_loop(
+            error_order,
+            |integrator| {
+                let (solution, error, derivative) = attempt(integrator)?;
+                let error = integrator.control().error_norm(&error, integrator.current(), &solution);
+                Ok(((solution, derivative), error))
+            },
+            |integrator, (solution, derivative)| integrator.accept(solution, derivative),
+        )
+    }
+
+    fn adaptive_tableau(&mut self, tableau: &ButcherTableau<Float>) -> Result<(), SolverError<Float>>
+    where
+        SolverError<Float>: From<Self::Failure>,
+    {
+        self.tableau_loop(
+            tableau,
+            |integrator| {
+                let (solution, error) = integrator.explicit_tableau(tableau)?;
+                let error = error.map_or(Float::default(), |error| {
+                    integrator.control().error_norm(&error, integrator.current(), &solution)
+                });
+                Ok((solution, error))
+            },
+            |integrator, solution| integrator.accept(solution, None),
+        )
+    }
+}
+
+// the pieces of an integrator the shared solve loop reads and adjusts
+pub trait ClampedSolve<Float> {
+    fn time(&mut self) -> &mut Float;
+
+    fn step_size(&mut self) -> &mut Float;
+
+    fn max_steps(&self) -> usize;
+
+    fn min_step(&mut self) -> &mut Float;
+
+    fn is_finite(&self) -> bool;
+
+    // called once the shortened last step has been snapped onto the final time
+    fn landed(&mut self, _landing: Float, _final_time: Float) {}
+}
+
+// steps towards the final time in whichever direction it lies and lands on it exactly, record sees the
+// integrator after every step and can end the solve early by breaking
+pub fn solve_clamped<Float, Integrator, Step, Output>(
+    integrator: &mut Integrator,
+    final_time: Float,
+    mut advance: impl FnMut(&mut Integrator) -> Result<Step, SolverError<Float>>,
+    mut record: impl FnMut(&mut Integrator, Step) -> ControlFlow<Output, Output>,
+) -> Result<Vec<Output>, SolverError<Float>>
+where
+    Float: Floating,
+    Integrator: ClampedSolve<Float>,
+{
+    let direction = (final_time - *integrator.time()).signum();
+    let step_size = integrator.step_size();
+    *step_size = step_size.abs() * direction;
+
+    let mut output = Vec::new();
+    while (final_time - *integrator.time()) * direction > Float::default() {
+        if output.len() >= integrator.max_steps() {
+            return Err(SolverError::TooManySteps { time: *integrator.time(), steps: output.len() });
+        }
+
+        // shorten the last step so it lands on the final time instead of overshooting it
+        let (remaining, stored) = (final_time - *integrator.time(), *integrator.step_size());
+        let clamped = stored.abs() >= remaining.abs();
+        if clamped {
+            *integrator.step_size() = remaining;
+        }
+        let landing = *integrator.time() + remaining;
+
+        // the shortened step is as short as the final time makes it, min_step only guards the steps the
+        // controller chooses
+        let min_step = *integrator.min_step();
+        if clamped {
+            *integrator.min_step() = Float::default();
+        }
+        let step = advance(integrator).and_then(|step| match integrator.is_finite() {
+            true => Ok(step),
+            false => Err(SolverError::NonFiniteState { time: *integrator.time() }),
+        });
+        *integrator.min_step() = min_step;
+        let step = match step {
+            Ok(step) => step,
+            // a failed step must not leave the shortened last step behind for the next solve
+            Err(error) => {
+                if clamped {
+                    *integrator.step_size() = stored;
+                }
+                return Err(error);
+            }
+        };
+
+        // a rejected step is cut short by the controller and has not reached the final time yet
+        if clamped && *integrator.time() == landing {
+            *integrator.time() = final_time;
+            *integrator.step_size() = stored;
+            integrator.landed(landing, final_time);
+        }
+
+        match record(integrator, step) {
+            ControlFlow::Continue(recorded) => output.push(recorded),
+            ControlFlow::Break(recorded) => {
+                output.push(recorded);
+                break;
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+// output times have to run away from the start in one direction, times equal to the start are allowed
+pub fn check_output_times<Float>(start: Float, times: &[Float]) -> Result<(), SolverError<Float>>
+where
+    Float: Floating,
+{
+    let Some(&final_time) = times.last() else {
+        return Ok(());
+    };
+
+    let direction = (final_time - start).signum();
+    let mut previous = start;
+    for (index, &time) in times.iter().enumerate() {
+        if !time.is_finite() || (time - previous) * direction < Float::default() {
+            return Err(SolverError::InvalidOutputTime { index, time });
+        }
+        previous = time;
+    }
+
+    Ok(())
+}
+
+#[derive(Clone, Copy)]
+pub struct HermiteSegment<Float, Vector> {
+    pub t0: Float,
+    pub t1: Float,
+    pub y0: Vector,
+    pub y1: Vector,
+    pub f0: Vector,
+    pub f1: Vector,
+}
+
+impl<Float, Vector> HermiteSegment<Float, Vector>
+where
+    Float: Floating,
+    Vector: StepState<Float>,
+{
+    pub fn build(start: (Float, Vector, Vector), end: (Float, Vector, Vector)) -> Self {
+        let ((t0, y0, f0), (t1, y1, f1)) = (start, end);
+        HermiteSegment { t0, t1, y0, y1, f0, f1 }
+    }
+
+    // cubic through both end states matching both end derivatives
+    pub fn interpolate(&self, time: Float) -> Vector {
+        let h = self.t1 - self.t0;
+        let s = (time - self.t0) / h;
+        let (s2, s3) = (s * s, s * s * s);
+        let (two, three) = (Float::floatify(2.), Float::floatify(3.));
+
+        let h00 = two * s3 - three * s2 + Float::floatify(1.);
+        let h10 = s3 - two * s2 + s;
+        let h01 = three * s2 - two * s3;
+        let h11 = s3 - s2;
+
+        let terms = [(&self.y0, h00), (&self.f0, h * h10), (&self.y1, h01), (&self.f1, h * h11)];
+        Vector::combine(&self.y0.zeroed(), &terms)
+    }
+
+    pub fn evaluate(&self, time: Float) -> Vector::Values {
+        self.interpolate(time).values()
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -30,6 +669,45 @@ where
     }
 }
 
+impl<Float, const N: usize> StateTypes<Float> for State<Float, N> {
+    type Values = [Float; N];
+    type Slice = [Float; N];
+    type Tolerance = [Float; N];
+}
+
+impl<Float, const N: usize> StepState<Float> for State<Float, N>
+where
+    Float: Floating,
+{
+    fn from_values(values: [Float; N]) -> Self {
+        State::build(values)
+    }
+
+    fn values(&self) -> [Float; N] {
+        self.inner
+    }
+
+    fn slice(&self) -> &[Float; N] {
+        &self.inner
+    }
+
+    fn default_control(&self) -> StepControl<Float, N> {
+        StepControl::build()
+    }
+
+    fn combine(base: &Self, terms: &[(&Self, Float)]) -> Self {
+        terms.iter().fold(*base, |sum, &(term, weight)| sum + *term * weight)
+    }
+
+    fn zeroed(&self) -> Self {
+        State::build([Float::default(); N])
+    }
+
+    fn components(&self) -> &[Float] {
+        &self.inner
+    }
+}
+
 impl<Float, const N: usize> Add for State<Float, N>
 where
     Float: Add<Output = Float> + Default + Copy,
@@ -64,14 +742,111 @@ where
 
 impl<Float, const N: usize> Norm<Float> for State<Float, N>
 where
-    Float: Mul<Output = Float> + AddAssign + Default + Copy,
+    Float: Floating,
 {
-    fn norm(&self) -> Float {
+    fn weighted_rms(&self, scale: &Self) -> Float {
         let mut sum = Float::default();
         (0..N).for_each(|idx| {
-            sum += self.inner[idx] * self.inner[idx];
+            let scaled = self.inner[idx] / scale.inner[idx];
+            sum += scaled * scaled;
         });
 
-        sum
+        (sum / Float::floatify(N.max(1) as f64)).sqrt()
+    }
+}
+
+// heap backed counterpart of State for systems sized at runtime
+#[derive(Clone)]
+pub struct RuntimeState<Float> {
+    pub inner: Vec<Float>,
+}
+
+impl<Float> RuntimeState<Float>
+where
+    Float: Floating,
+{
+    pub const fn build(inner: Vec<Float>) -> Self {
+        RuntimeState { inner }
+    }
+
+    pub fn values(&self) -> Vec<Float> {
+        self.inner.clone()
+    }
+}
+
+// every operand has the length of the state, evaluations of the dynamics are checked before they get here
+impl<Float> StateTypes<Float> for RuntimeState<Float> {
+    type Values = Vec<Float>;
+    type Slice = [Float];
+    type Tolerance = Vec<Float>;
+}
+
+impl<Float> StepState<Float> for RuntimeState<Float>
+where
+    Float: Floating,
+{
+    fn from_values(values: Vec<Float>) -> Self {
+        RuntimeState::build(values)
+    }
+
+    fn values(&self) -> Vec<Float> {
+        self.inner.clone()
+    }
+
+    fn slice(&self) -> &[Float] {
+        &self.inner
+    }
+
+    fn default_control(&self) -> RuntimeStepControl<Float> {
+        RuntimeStepControl::build(self.inner.len())
+    }
+
+    // base plus every term times its weight in a single pass, so no intermediate vectors are allocated
+    fn combine(base: &Self, terms: &[(&Self, Float)]) -> Self {
+        let mut result = base.inner.clone();
+        terms.iter().for_each(|&(term, weight)| {
+            result.iter_mut().zip(&term.inner).for_each(|(value, &slope)| *value += slope * weight);
+        });
+
+        RuntimeState::build(result)
+    }
+
+    fn zeroed(&self) -> Self {
+        RuntimeState::build(vec![Float::default(); self.inner.len()])
+    }
+
+    fn components(&self) -> &[Float] {
+        &self.inner
+    }
+}
+
+impl<Float, const N: usize, System> Evaluate<Float, System> for State<Float, N>
+where
+    Float: Floating,
+    System: OdeSystem<Float, N>,
+{
+    type Failure = Infallible;
+
+    fn dynamics(system: &mut System, time: Float, state: &Self) -> Result<Self, Infallible> {
+        Ok(State::build(system.dynamics(time, &state.inner)))
+    }
+}
+
+impl<Float, System> Evaluate<Float, System> for RuntimeState<Float>
+where
+    Float: Floating,
+    System: RuntimeSystem<Float>,
+{
+    type Failure = SolverError<Float>;
+
+    // a derivative of the wrong length would otherwise be cut to size when the stages are combined
+    fn dynamics(system: &mut System, time: Float, state: &Self) -> Result<Self, SolverError<Float>> {
+        let derivative = system.dynamics(time, &state.inner);
+        if derivative.len() != state.inner.len() {
+            let (expected, found) = (state.inner.len(), derivative.len());
+            return Err(SolverError::DimensionMismatch { time, expected, found });
+        }
+
+        Ok(RuntimeState::build(derivative))
     }
 }
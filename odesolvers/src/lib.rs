@@ -1,7 +1,23 @@
+pub mod butcher;
+pub mod error;
+pub mod event;
+pub mod fitting;
+pub mod implicit;
+pub mod lyapunov;
+pub mod nystrom;
 pub mod plot;
+pub mod random;
 pub mod runge_kutta;
+pub mod runtime_sized;
+pub mod sensitivity;
+pub mod shooting;
+pub mod solution;
+pub mod stochastic;
+pub mod symplectic;
+pub mod system;
 pub mod vector;
 
 mod integration_shared;
+mod linear_algebra;
 mod plot_utils;
 mod scalar;
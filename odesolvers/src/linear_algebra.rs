@@ -0,0 +1,135 @@
+use std::ops::Index;
+use std::ops::IndexMut;
+
+use crate::scalar::Floating;
+
+#[derive(Clone, Debug)]
+pub struct Matrix<Float> {
+    pub rows: usize,
+    pub cols: usize,
+    pub data: Vec<Float>,
+}
+
+impl<Float> Matrix<Float>
+where
+    Float: Floating,
+{
+    pub fn build(rows: usize, cols: usize) -> Self {
+        Matrix { rows, cols, data: vec![Float::default(); rows * cols] }
+    }
+
+    pub fn from_rows<const N: usize, const M: usize>(rows: &[[Float; M]; N]) -> Self {
+        let mut matrix = Self::build(N, M);
+        (0..N).for_each(|row| (0..M).for_each(|col| matrix[(row, col)] = rows[row][col]));
+        matrix
+    }
+
+    // magnitude of the dominant eigenvalue by power iteration, only a rough estimate for complex pairs
+    pub fn spectral_radius(&self) -> Float {
+        const ITERATIONS: usize = 20;
+
+        let mut vector: Vec<Float> =
+            (0..self.cols).map(|idx| Float::floatify(1. / (idx + 1) as f64)).collect();
+        let mut radius = Float::default();
+        for _ in 0..ITERATIONS {
+            let product: Vec<Float> = (0..self.rows)
+                .map(|row| {
+                    (0..self.cols).fold(Float::default(), |sum, col| sum + self[(row, col)] * vector[col])
+                })
+                .collect();
+            let length = |values: &[Float]| {
+                values.iter().fold(Float::default(), |sum, &value| sum + value * value).sqrt()
+            };
+            let (before, after) = (length(&vector), length(&product));
+            if after == Float::default() || !after.is_finite() {
+                return after;
+            }
+
+            radius = after / before;
+            vector = product.into_iter().map(|value| value / after).collect();
+        }
+
+        radius
+    }
+
+    // lu factorisation with partial pivoting, none when the matrix is numerically singular
+    pub fn lu(mut self) -> Option<LuDecomposition<Float>> {
+        let size = self.rows;
+        let mut pivots: Vec<usize> = (0..size).collect();
+        for col in 0..size {
+            let pivot = (col..size).max_by(|&a, &b| {
+                self[(a, col)].abs().partial_cmp(&self[(b, col)].abs()).unwrap_or(std::cmp::Ordering::Equal)
+            })?;
+            if self[(pivot, col)] == Float::default() || !self[(pivot, col)].is_finite() {
+                return None;
+            }
+
+            if pivot != col {
+                (0..size).for_each(|idx| self.data.swap(pivot * size + idx, col * size + idx));
+                pivots.swap(pivot, col);
+            }
+
+            for row in (col + 1)..size {
+                let factor = self[(row, col)] / self[(col, col)];
+                self[(row, col)] = factor;
+                for idx in (col + 1)..size {
+                    let update = factor * self[(col, idx)];
+                    self[(row, idx)] -= update;
+                }
+            }
+        }
+
+        Some(LuDecomposition { lu: self, pivots })
+    }
+}
+
+impl<Float> Index<(usize, usize)> for Matrix<Float> {
+    type Output = Float;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        &self.data[row * self.cols + col]
+    }
+}
+
+impl<Float> IndexMut<(usize, usize)> for Matrix<Float> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        &mut self.data[row * self.cols + col]
+    }
+}
+
+pub struct LuDecomposition<Float> {
+    lu: Matrix<Float>,
+    pivots: Vec<usize>,
+}
+
+impl<Float> LuDecomposition<Float>
+where
+    Float: Floating,
+{
+    pub fn solve(&self, rhs: &[Float]) -> Vec<Float> {
+        let size = self.lu.rows;
+        let mut solution: Vec<Float> = self.pivots.iter().map(|&pivot| rhs[pivot]).collect();
+
+        for row in 0..size {
+            for col in 0..row {
+                let update = self.lu[(row, col)] * solution[col];
+                solution[row] -= update;
+            }
+        }
+        for row in (0..size).rev() {
+            for col in (row + 1)..size {
+                let update = self.lu[(row, col)] * solution[col];
+                solution[row] -= update;
+            }
+            solution[row] /= self.lu[(row, row)];
+        }
+
+        solution
+    }
+
+    pub fn solve_array<const N: usize>(&self, rhs: &[Float; N]) -> [Float; N] {
+        let mut result = [Float::default(); N];
+        result.copy_from_slice(&self.solve(rhs));
+        result
+    }
+}
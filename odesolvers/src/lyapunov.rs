@@ -0,0 +1,137 @@
+use crate::error::SolverError;
+use crate::integration_shared::HermiteSegment;
+use crate::integration_shared::State;
+use crate::integration_shared::StepState;
+use crate::runge_kutta::Integrator;
+use crate::scalar::Floating;
+use crate::system::OdeSystem;
+
+#[derive(Clone, Debug)]
+pub struct LyapunovSpectrum<Float, const N: usize> {
+    // largest first, in units of one over time
+    pub exponents: [Float; N],
+    // running estimates after every reorthonormalisation, against the time averaged over
+    pub history: Vec<(Float, [Float; N])>,
+}
+
+// follows the orbit from the integrator's current state with fixed rk4 steps of its delta time and carries
+// N tangent vectors along, reorthonormalised every interval steps, let any transient die out beforehand
+pub fn lyapunov_spectrum<Float, const N: usize, System>(
+    integrator: &mut Integrator<Float, N, System>,
+    duration: Float,
+    interval: usize,
+) -> Result<LyapunovSpectrum<Float, N>, SolverError<Float>>
+where
+    Float: Floating,
+    System: OdeSystem<Float, N>,
+{
+    let (dt, start) = (integrator.delta_time(), integrator.curr_time());
+    if !dt.is_finite() || dt == Float::default() {
+        return Err(SolverError::InvalidStepSize { step: dt });
+    }
+    // every exponent changes sign when the orbit is followed backwards
+    if dt < Float::default() || duration < Float::default() {
+        return Err(SolverError::BackwardIntegration { time: start, final_time: start - duration.abs() });
+    }
+    let steps = (duration / dt).to_f64().round() as usize;
+    if steps == 0 || !duration.is_finite() {
+        return Err(SolverError::InvalidDuration { duration, step: dt });
+    }
+    let interval = interval.max(1);
+
+    let mut tangents = [State::build([Float::default(); N]); N];
+    (0..N).for_each(|idx| tangents[idx].inner[idx] = Float::floatify(1.));
+    let mut stretching = [Float::default(); N];
+    let mut history = Vec::with_capacity(steps / interval);
+
+    for step in 1..=steps {
+        let (t0, y0) = (integrator.curr_time(), integrator.state());
+        let f0 = integrator.system().dynamics(t0, &y0);
+        let y1 = integrator.step();
+        let t1 = integrator.curr_time();
+        if !State::build(y1).is_finite() {
+            return Err(SolverError::NonFiniteState { time: t1 });
+        }
+        let f1 = integrator.system().dynamics(t1, &y1);
+
+        // the tangent equations need the orbit halfway through the step, the hermite cubic is accurate enough
+        // to keep them fourth order
+        let segment = HermiteSegment::build(
+            (t0, State::build(y0), State::build(f0)),
+            (t1, State::build(y1), State::build(f1)),
+        );
+        let (dt, middle) = (t1 - t0, t0 + (t1 - t0) * Float::floatify(0.5));
+        let jacobians = [
+            integrator.system().jacobian(t0, &y0),
+            integrator.system().jacobian(middle, &segment.evaluate(middle)),
+            integrator.system().jacobian(t1, &y1),
+        ];
+        tangents.iter_mut().for_each(|tangent| *tangent = tangent_step(&jacobians, *tangent, dt));
+
+        if step % interval == 0 || step == steps {
+            let time = integrator.curr_time();
+            let stretches = orthonormalise(&mut tangents).ok_or(SolverError::DegenerateTangents { time })?;
+            (0..N).for_each(|idx| stretching[idx] += stretches[idx].ln());
+
+            let elapsed = time - start;
+            let mut estimate = [Float::default(); N];
+            (0..N).for_each(|idx| estimate[idx] = stretching[idx] / elapsed);
+            history.push((elapsed, estimate));
+        }
+    }
+
+    let exponents = history.last().map(|&(_, estimate)| estimate).unwrap_or([Float::default(); N]);
+    Ok(LyapunovSpectrum { exponents, history })
+}
+
+fn tangent_step<Float, const N: usize>(
+    [start, middle, end]: &[[[Float; N]; N]; 3],
+    tangent: State<Float, N>,
+    dt: Float,
+) -> State<Float, N>
+where
+    Float: Floating,
+{
+    let product = |jacobian: &[[Float; N]; N], vector: State<Float, N>| {
+        let mut result = [Float::default(); N];
+        (0..N).for_each(|row| {
+            result[row] =
+                (0..N).fold(Float::default(), |sum, col| sum + jacobian[row][col] * vector.inner[col]);
+        });
+        State::build(result)
+    };
+
+    let half = dt * Float::floatify(0.5);
+    let k1 = product(start, tangent);
+    let k2 = product(middle, tangent + k1 * half);
+    let k3 = product(middle, tangent + k2 * half);
+    let k4 = product(end, tangent + k3 * dt);
+
+    tangent + (k1 + k4) * (dt / Float::floatify(6.)) + (k2 + k3) * (dt / Float::floatify(3.))
+}
+
+// modified gram-schmidt, the lengths removed are the diagonal of R in the qr factorisation, none once a vector
+// has collapsed onto the others or overflowed
+fn orthonormalise<Float, const N: usize>(vectors: &mut [State<Float, N>; N]) -> Option<[Float; N]>
+where
+    Float: Floating,
+{
+    let dot = |a: &State<Float, N>, b: &State<Float, N>| {
+        (0..N).fold(Float::default(), |sum, idx| sum + a.inner[idx] * b.inner[idx])
+    };
+
+    let mut lengths = [Float::default(); N];
+    for current in 0..N {
+        for previous in 0..current {
+            let projection = dot(&vectors[current], &vectors[previous]);
+            vectors[current] = vectors[current] + vectors[previous] * -projection;
+        }
+        lengths[current] = dot(&vectors[current], &vectors[current]).sqrt();
+        if !lengths[current].is_finite() || lengths[current] == Float::default() {
+            return None;
+        }
+        vectors[current] = vectors[current] * (Float::floatify(1.) / lengths[current]);
+    }
+
+    Some(lengths)
+}
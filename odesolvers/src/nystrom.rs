@@ -0,0 +1,397 @@
+use std::ops::ControlFlow;
+use std::rc::Rc;
+
+use crate::butcher::ButcherTableau;
+use crate::error::SolverError;
+use crate::integration_shared::solve_clamped;
+use crate::integration_shared::AdaptiveStep;
+use crate::integration_shared::ClampedSolve;
+use crate::integration_shared::State;
+use crate::integration_shared::StepControl;
+use crate::integration_shared::StepState;
+use crate::scalar::Floating;
+use crate::symplectic::PhasePoint;
+use crate::system::Autonomous;
+use crate::system::Conservative;
+use crate::system::SecondOrderSystem;
+
+// times, positions and velocities, one entry per step
+pub type Trajectories<Float, const N: usize> = (Vec<Float>, Vec<[Float; N]>, Vec<[Float; N]>);
+
+// position, velocity, their error estimates and the last stage acceleration
+type NystromStep<Float, const N: usize> =
+    (State<Float, N>, State<Float, N>, Option<(State<Float, N>, State<Float, N>)>, Option<State<Float, N>>);
+
+// runge-kutta-nystrom, integrates x'' = f(t, x, x') without rewriting it as a first order system
+pub struct NystromIntegrator<Float, const N: usize, System> {
+    position: State<Float, N>,
+    velocity: State<Float, N>,
+    dt: Float,
+    system: System,
+    time: Float,
+    acceleration: Option<State<Float, N>>,
+    control: StepControl<Float, N>,
+    // shared so a dynamic step can hold on to it while it moves the integrator
+    dormand_prince: Rc<ButcherTableau<Float>>,
+}
+
+impl<Float, const N: usize, Acceleration> NystromIntegrator<Float, N, Autonomous<Acceleration>>
+where
+    Float: Floating,
+    Acceleration: FnMut(&[Float; N], &[Float; N]) -> [Float; N],
+{
+    pub fn build(
+        position: [Float; N],
+        velocity: [Float; N],
+        delta_time: Float,
+        acceleration: Acceleration,
+    ) -> Self {
+        Self::build_system(position, velocity, delta_time, Autonomous(acceleration))
+    }
+}
+
+impl<Float, const N: usize, Acceleration> NystromIntegrator<Float, N, Conservative<Acceleration>>
+where
+    Float: Floating,
+    Acceleration: FnMut(&[Float; N]) -> [Float; N],
+{
+    pub fn build_conservative(
+        position: [Float; N],
+        velocity: [Float; N],
+        delta_time: Float,
+        acceleration: Acceleration,
+    ) -> Self {
+        Self::build_system(position, velocity, delta_time, Conservative(acceleration))
+    }
+}
+
+impl<Float, const N: usize, Acceleration> NystromIntegrator<Float, N, Acceleration>
+where
+    Float: Floating,
+    Acceleration: FnMut(Float, &[Float; N], &[Float; N]) -> [Float; N],
+{
+    pub fn build_driven(
+        position: [Float; N],
+        velocity: [Float; N],
+        delta_time: Float,
+        acceleration: Acceleration,
+    ) -> Self {
+        Self::build_system(position, velocity, delta_time, acceleration)
+    }
+}
+
+impl<Float, const N: usize, System> NystromIntegrator<Float, N, System>
+where
+    Float: Floating,
+    System: SecondOrderSystem<Float, N>,
+{
+    pub fn build_system(
+        position: [Float; N],
+        velocity: [Float; N],
+        delta_time: Float,
+        system: System,
+    ) -> Self {
+        NystromIntegrator {
+            position: State::build(position),
+            velocity: State::build(velocity),
+            dt: delta_time,
+            system,
+            time: Float::default(),
+            acceleration: None,
+            control: StepControl::build(),
+            dormand_prince: Rc::new(ButcherTableau::dormand_prince()),
+        }
+    }
+
+    pub const fn position(&self) -> [Float; N] {
+        self.position.values()
+    }
+
+    pub const fn velocity(&self) -> [Float; N] {
+        self.velocity.values()
+    }
+
+    pub const fn state(&self) -> PhasePoint<Float, N> {
+        (self.position.values(), self.velocity.values())
+    }
+
+    pub const fn delta_time(&self) -> Float {
+        self.dt
+    }
+
+    pub const fn curr_time(&self) -> Float {
+        self.time
+    }
+
+    pub fn set_time(&mut self, time: Float) -> &mut Self {
+        self.time = time;
+        self.acceleration = None;
+        self
+    }
+
+    pub fn set_state(&mut self, position: [Float; N], velocity: [Float; N]) -> &mut Self {
+        self.position = State::build(position);
+        self.velocity = State::build(velocity);
+        self.acceleration = None;
+        self
+    }
+
+    pub fn system(&mut self) -> &mut System {
+        &mut self.system
+    }
+
+    // tolerances apply to a position and its velocity alike
+    pub fn set_control(&mut self) -> &mut StepControl<Float, N> {
+        &mut self.control
+    }
+
+    // classical fourth order nystrom, three evaluations when the acceleration ignores the velocity
+    pub fn step(&mut self) -> PhasePoint<Float, N> {
+        let half = Float::floatify(0.5);
+        let (dt, position, velocity) = (self.dt, self.position, self.velocity);
+        let half_drift = position + velocity * (dt * half);
+
+        let first = self.evaluate(Float::default(), position, velocity);
+        let second = self.evaluate(
+            dt * half,
+            half_drift + first * (dt * dt * Float::floatify(0.125)),
+            velocity + first * (dt * half),
+        );
+
+        (self.position, self.velocity) = match self.system.velocity_dependent() {
+            true => {
+                let third = self.evaluate(
+                    dt * half,
+                    half_drift + first * (dt * dt * Float::floatify(0.125)),
+                    velocity + second * (dt * half),
+                );
+                let fourth = self.evaluate(
+                    dt,
+                    position + velocity * dt + third * (dt * dt * half),
+                    velocity + third * dt,
+                );
+                let sixth = dt / Float::floatify(6.);
+                (
+                    position + velocity * dt + (first + second + third) * (dt * sixth),
+                    velocity
+                        + (first + second * Float::floatify(2.) + third * Float::floatify(2.) + fourth)
+                            * sixth,
+                )
+            }
+            false => {
+                let third = self.evaluate(dt, position + velocity * dt + second * (dt * dt * half), velocity);
+                let sixth = dt / Float::floatify(6.);
+                (
+                    position + velocity * dt + (first + second * Float::floatify(2.)) * (dt * sixth),
+                    velocity + (first + second * Float::floatify(4.) + third) * sixth,
+                )
+            }
+        };
+        self.time += self.dt;
+        self.acceleration = None;
+
+        self.state()
+    }
+
+    pub fn dynamic_step(&mut self) -> Result<PhasePoint<Float, N>, SolverError<Float>> {
+        let tableau = Rc::clone(&self.dormand_prince);
+        self.adaptive_tableau_step(&tableau)
+    }
+
+    pub fn tableau_step(&mut self, tableau: &ButcherTableau<Float>) -> PhasePoint<Float, N> {
+        let (position, velocity, _, _) = self.nystrom_tableau(tableau);
+        self.time += self.dt;
+        (self.position, self.velocity) = (position, velocity);
+        self.acceleration = None;
+
+        self.state()
+    }
+
+    pub fn adaptive_tableau_step(
+        &mut self,
+        tableau: &ButcherTableau<Float>,
+    ) -> Result<PhasePoint<Float, N>, SolverError<Float>> {
+        self.tableau_loop(
+            tableau,
+            |integrator| {
+                let (position, velocity, error, last) = integrator.nystrom_tableau(tableau);
+                let error =
+                    error.map_or(Float::default(), |error| integrator.error_norm(error, position, velocity));
+                Ok(((position, velocity, last), error))
+            },
+            |integrator, (position, velocity, last)| {
+                (integrator.position, integrator.velocity) = (position, velocity);
+                integrator.acceleration = last.filter(|_| tableau.is_fsal());
+            },
+        )?;
+
+        Ok(self.state())
+    }
+
+    pub fn solve_until(
+        &mut self,
+        final_time: Float,
+    ) -> Result<Vec<PhasePoint<Float, N>>, SolverError<Float>> {
+        Ok(self.solve_with_time(final_time)?.into_iter().map(|(_, point)| point).collect())
+    }
+
+    pub fn solve_with_time(
+        &mut self,
+        final_time: Float,
+    ) -> Result<Vec<(Float, PhasePoint<Float, N>)>, SolverError<Float>> {
+        self.solve_by(final_time, |integrator| Ok(integrator.step()))
+    }
+
+    pub fn solve_dynamic_until(
+        &mut self,
+        final_time: Float,
+    ) -> Result<Vec<PhasePoint<Float, N>>, SolverError<Float>> {
+        Ok(self.solve_dynamic_with_time(final_time)?.into_iter().map(|(_, point)| point).collect())
+    }
+
+    pub fn solve_dynamic_with_time(
+        &mut self,
+        final_time: Float,
+    ) -> Result<Vec<(Float, PhasePoint<Float, N>)>, SolverError<Float>> {
+        self.solve_by(final_time, Self::dynamic_step)
+    }
+
+    pub fn solve_tableau_until(
+        &mut self,
+        final_time: Float,
+        tableau: &ButcherTableau<Float>,
+    ) -> Result<Vec<PhasePoint<Float, N>>, SolverError<Float>> {
+        Ok(self.solve_tableau_with_time(final_time, tableau)?.into_iter().map(|(_, point)| point).collect())
+    }
+
+    pub fn solve_tableau_with_time(
+        &mut self,
+        final_time: Float,
+        tableau: &ButcherTableau<Float>,
+    ) -> Result<Vec<(Float, PhasePoint<Float, N>)>, SolverError<Float>> {
+        self.solve_by(final_time, |integrator| integrator.adaptive_tableau_step(tableau))
+    }
+
+    // position and velocity trajectories side by side, sampled at every step
+    pub fn solve_trajectories(
+        &mut self,
+        final_time: Float,
+    ) -> Result<Trajectories<Float, N>, SolverError<Float>> {
+        let solution = self.solve_dynamic_with_time(final_time)?;
+        let times = solution.iter().map(|&(time, _)| time).collect();
+        let positions = solution.iter().map(|&(_, (position, _))| position).collect();
+        let velocities = solution.iter().map(|&(_, (_, velocity))| velocity).collect();
+
+        Ok((times, positions, velocities))
+    }
+
+    fn solve_by(
+        &mut self,
+        final_time: Float,
+        advance: impl FnMut(&mut Self) -> Result<PhasePoint<Float, N>, SolverError<Float>>,
+    ) -> Result<Vec<(Float, PhasePoint<Float, N>)>, SolverError<Float>> {
+        solve_clamped(self, final_time, advance, |integrator, point| {
+            ControlFlow::Continue((integrator.time, point))
+        })
+    }
+
+    fn evaluate(
+        &mut self,
+        offset: Float,
+        position: State<Float, N>,
+        velocity: State<Float, N>,
+    ) -> State<Float, N> {
+        State::build(self.system.acceleration(self.time + offset, &position.inner, &velocity.inner))
+    }
+
+    // any runge-kutta tableau applied to the first order form, with the position weights folded through a
+    fn nystrom_tableau(&mut self, tableau: &ButcherTableau<Float>) -> NystromStep<Float, N> {
+        let (dt, position, velocity) = (self.dt, self.position, self.velocity);
+        let zero = State::build([Float::default(); N]);
+        // the position weights are the velocity weights integrated once more, a * a and b * a
+        let folded = |weights: &[Float], stage: usize| {
+            (0..weights.len()).fold(Float::default(), |sum, idx| {
+                sum + weights[idx] * tableau.a[idx].get(stage).copied().unwrap_or_default()
+            })
+        };
+
+        let mut stages: Vec<State<Float, N>> = Vec::with_capacity(tableau.stages());
+        (0..tableau.stages()).for_each(|stage| {
+            let weights = &tableau.a[stage];
+            let (drift, kick) = (0..stage).fold((zero, zero), |(drift, kick), prev| {
+                (drift + stages[prev] * folded(weights, prev), kick + stages[prev] * weights[prev])
+            });
+            let acceleration = match (stage, self.acceleration) {
+                (0, Some(acceleration)) => acceleration,
+                _ => self.evaluate(
+                    dt * tableau.c[stage],
+                    position + velocity * (dt * tableau.c[stage]) + drift * (dt * dt),
+                    velocity + kick * dt,
+                ),
+            };
+            stages.push(acceleration);
+        });
+
+        let weighted = |weights: &[Float], folding: bool| {
+            (0..tableau.stages()).fold(zero, |sum, stage| {
+                let weight = if folding { folded(weights, stage) } else { weights[stage] };
+                sum + stages[stage] * weight
+            })
+        };
+        let next_position = position + velocity * dt + weighted(&tableau.b, true) * (dt * dt);
+        let next_velocity = velocity + weighted(&tableau.b, false) * dt;
+        let error = tableau.b_hat.as_ref().map(|b_hat| {
+            let weights: Vec<Float> = tableau.b.iter().zip(b_hat).map(|(&b, &b_hat)| b - b_hat).collect();
+            (weighted(&weights, true) * (dt * dt), weighted(&weights, false) * dt)
+        });
+
+        (next_position, next_velocity, error, stages.last().copied())
+    }
+
+    // both halves are scaled like ode45 and folded into one root mean square
+    fn error_norm(
+        &self,
+        (position_error, velocity_error): (State<Float, N>, State<Float, N>),
+        position: State<Float, N>,
+        velocity: State<Float, N>,
+    ) -> Float {
+        let position_norm = self.control.error_norm(&position_error, &self.position, &position);
+        let velocity_norm = self.control.error_norm(&velocity_error, &self.velocity, &velocity);
+
+        ((position_norm * position_norm + velocity_norm * velocity_norm) * Float::floatify(0.5)).sqrt()
+    }
+}
+
+impl<Float, const N: usize, System> ClampedSolve<Float> for NystromIntegrator<Float, N, System>
+where
+    Float: Floating,
+{
+    fn time(&mut self) -> &mut Float {
+        &mut self.time
+    }
+
+    fn step_size(&mut self) -> &mut Float {
+        &mut self.dt
+    }
+
+    fn max_steps(&self) -> usize {
+        self.control.max_steps
+    }
+
+    fn min_step(&mut self) -> &mut Float {
+        &mut self.control.min_step
+    }
+
+    fn is_finite(&self) -> bool {
+        self.position.is_finite() && self.velocity.is_finite()
+    }
+}
+
+impl<Float, const N: usize, System> AdaptiveStep<Float, [Float; N]> for NystromIntegrator<Float, N, System>
+where
+    Float: Floating,
+{
+    fn control(&self) -> &StepControl<Float, N> {
+        &self.control
+    }
+}
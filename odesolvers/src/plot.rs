@@ -129,6 +129,14 @@ impl Plot {
         true
     }
 
+    // one dot per point, returns how many fell inside the bounds
+    pub fn plot_scatter<T>(&mut self, points: impl IntoIterator<Item = (T, T)>) -> usize
+    where
+        T: Floating,
+    {
+        points.into_iter().filter(|&(x, y)| self.plot_point(x, y)).count()
+    }
+
     pub fn plot_line<T>(&mut self, x0: T, y0: T, x1: T, y1: T) -> bool
     where
         T: Floating,
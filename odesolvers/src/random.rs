@@ -0,0 +1,56 @@
+// xoshiro256**, seeded through splitmix64 so that any seed, zero included, gives a usable state
+#[derive(Clone, Debug)]
+pub struct Random {
+    state: [u64; 4],
+    spare: Option<f64>,
+}
+
+impl Random {
+    pub fn build(seed: u64) -> Self {
+        let mut mixer = seed;
+        let mut state = [0; 4];
+        state.iter_mut().for_each(|word| *word = splitmix64(&mut mixer));
+
+        Random { state, spare: None }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let result = self.state[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let shifted = self.state[1] << 17;
+
+        self.state[2] ^= self.state[0];
+        self.state[3] ^= self.state[1];
+        self.state[1] ^= self.state[2];
+        self.state[0] ^= self.state[3];
+        self.state[2] ^= shifted;
+        self.state[3] = self.state[3].rotate_left(45);
+
+        result
+    }
+
+    // uniform on [0, 1), from the top 53 bits
+    pub fn uniform(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1. / (1u64 << 53) as f64)
+    }
+
+    // standard normal, box-muller hands out its second value on the next call
+    pub fn normal(&mut self) -> f64 {
+        if let Some(spare) = self.spare.take() {
+            return spare;
+        }
+
+        // 1 - uniform lies in (0, 1], so the logarithm stays finite
+        let radius = (-2. * (1. - self.uniform()).ln()).sqrt();
+        let angle = 2. * std::f64::consts::PI * self.uniform();
+        self.spare = Some(radius * angle.sin());
+        radius * angle.cos()
+    }
+}
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut mixed = *state;
+    mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94d049bb133111eb);
+    mixed ^ (mixed >> 31)
+}
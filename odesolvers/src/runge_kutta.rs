@@ -1,31 +1,136 @@
-use crate::integration_shared::DynamicsFunction;
+use std::ops::ControlFlow;
+use std::rc::Rc;
+
+use crate::butcher::ButcherTableau;
+use crate::error::SolverError;
+use crate::event::StateEvent;
+use crate::event::StateEventRecord;
+use crate::integration_shared::check_output_times;
+use crate::integration_shared::solve_clamped;
+use crate::integration_shared::AdaptiveStep;
+use crate::integration_shared::ClampedSolve;
+use crate::integration_shared::Control;
+use crate::integration_shared::Evaluate;
+use crate::integration_shared::HermiteSegment;
 use crate::integration_shared::IntegrationStep;
-use crate::integration_shared::Norm;
 use crate::integration_shared::State;
+use crate::integration_shared::StateTypes;
+use crate::integration_shared::StepControl;
+use crate::integration_shared::StepState;
+use crate::integration_shared::Tolerances;
 use crate::scalar::Floating;
+use crate::solution::Solution;
+use crate::solution::StateSolution;
+use crate::system::Autonomous;
+use crate::system::DelaySystem;
+use crate::system::OdeSystem;
 
-#[derive(Clone, Copy)]
-pub struct Integrator<Float, const N: usize> {
-    state: State<Float, N>,
+// corrected state, its error against the prediction and the derivative at the corrected state
+type AdamsStep<Vector> = (Vector, Vector, Option<Vector>);
+
+// time, state and derivative at one end of a step
+type SegmentEnd<Float, Vector> = (Float, Vector, Vector);
+
+pub type Integrator<Float, const N: usize, System> = OdeIntegrator<Float, State<Float, N>, System>;
+
+// the explicit solvers for any kind of state, used through Integrator for fixed size states and through
+// RuntimeIntegrator for states sized at runtime
+pub struct OdeIntegrator<Float, Vector, System>
+where
+    Vector: StateTypes<Float>,
+{
+    state: Vector,
     dt: Float,
-    ddt: DynamicsFunction<Float, N>,
+    system: System,
     time: Float,
+    derivative: Option<Vector>,
+    control: Control<Float, Vector::Tolerance>,
+    events: Vec<StateEvent<Float, Vector::Slice>>,
+    event_log: Vec<StateEventRecord<Float, Vector::Values>>,
+    history: Vec<(Float, Vector)>,
+    adams_order: usize,
+    steps_at_order: usize,
+    extrapolation_columns: usize,
 }
 
-impl<Float, const N: usize> Integrator<Float, N>
+impl<Float, const N: usize, Dynamics> Integrator<Float, N, Autonomous<Dynamics>>
 where
     Float: Floating + Default + Copy,
+    Dynamics: FnMut(&[Float; N]) -> [Float; N],
 {
-    const TOLERANCE: f64 = 1e-8;
+    pub fn build(state: [Float; N], delta_time: Float, dynamics: Dynamics) -> Self {
+        Self::build_system(state, delta_time, Autonomous(dynamics))
+    }
+}
 
-    pub fn build(state: [Float; N], delta_time: Float, dynamics: DynamicsFunction<Float, N>) -> Self {
-        Integrator { state: State::build(state), dt: delta_time, ddt: dynamics, time: Float::default() }
+impl<Float, const N: usize, Dynamics> Integrator<Float, N, Dynamics>
+where
+    Float: Floating + Default + Copy,
+    Dynamics: FnMut(Float, &[Float; N]) -> [Float; N],
+{
+    pub fn build_driven(state: [Float; N], delta_time: Float, dynamics: Dynamics) -> Self {
+        Self::build_system(state, delta_time, dynamics)
+    }
+}
+
+impl<Float, const N: usize, System> Integrator<Float, N, System>
+where
+    Float: Floating + Default + Copy,
+    System: OdeSystem<Float, N>,
+{
+    pub fn build_system(state: [Float; N], delta_time: Float, system: System) -> Self {
+        Self::build_state(State::build(state), delta_time, system)
     }
 
     pub const fn state(&self) -> [Float; N] {
         self.state.values()
     }
 
+    pub fn set_state(&mut self, state: [Float; N]) -> &mut Self {
+        self.replace_state(State::build(state));
+        self
+    }
+
+    pub fn step(&mut self) -> [Float; N] {
+        let Ok(state) = self.fixed_step();
+        state
+    }
+
+    pub fn tableau_step(&mut self, tableau: &ButcherTableau<Float>) -> [Float; N] {
+        let Ok(state) = self.fixed_tableau_step(tableau);
+        state
+    }
+}
+
+impl<Float, Vector, System> OdeIntegrator<Float, Vector, System>
+where
+    Float: Floating,
+    Vector: Evaluate<Float, System>,
+    SolverError<Float>: From<Vector::Failure>,
+{
+    const ADAMS_STARTUP: usize = 4;
+    const ADAMS_MAX_ORDER: usize = 12;
+    const ADAMS_GROWTH: f64 = 2.;
+    const EXTRAPOLATION_START: usize = 5;
+    const EXTRAPOLATION_MAX_COLUMNS: usize = 8;
+
+    pub(crate) fn build_state(state: Vector, delta_time: Float, system: System) -> Self {
+        OdeIntegrator {
+            control: state.default_control(),
+            state,
+            dt: delta_time,
+            system,
+            time: Float::default(),
+            derivative: None,
+            events: Vec::new(),
+            event_log: Vec::new(),
+            history: Vec::new(),
+            adams_order: Self::ADAMS_STARTUP,
+            steps_at_order: 0,
+            extrapolation_columns: Self::EXTRAPOLATION_START,
+        }
+    }
+
     pub const fn delta_time(&self) -> Float {
         self.dt
     }
@@ -34,83 +139,1020 @@ where
         self.time
     }
 
-    pub fn step(&mut self) -> [Float; N] {
+    pub const fn adams_order(&self) -> usize {
+        self.adams_order
+    }
+
+    pub const fn extrapolation_columns(&self) -> usize {
+        self.extrapolation_columns
+    }
+
+    pub fn set_time(&mut self, time: Float) -> &mut Self {
+        self.time = time;
+        self.derivative = None;
+        self.history.clear();
+        self
+    }
+
+    pub(crate) fn replace_state(&mut self, state: Vector) {
+        self.state = state;
+        self.derivative = None;
+        self.history.clear();
+    }
+
+    pub fn system(&mut self) -> &mut System {
+        &mut self.system
+    }
+
+    // one tolerance per component, sized with the state
+    pub fn set_control(&mut self) -> &mut Control<Float, Vector::Tolerance> {
+        &mut self.control
+    }
+
+    pub fn add_event(&mut self, event: StateEvent<Float, Vector::Slice>) -> &mut Self {
+        self.events.push(event);
+        self
+    }
+
+    pub fn event_log(&self) -> &[StateEventRecord<Float, Vector::Values>] {
+        &self.event_log
+    }
+
+    // the rk4 step behind step, which hands its result back however the state type is handed in
+    pub(crate) fn fixed_step(&mut self) -> Result<Vector::Values, Vector::Failure> {
+        self.state = self.runge_kutta_4()?;
         self.time += self.dt;
-        self.state = State::build(self.runge_kutta_4());
-        self.state()
+        self.derivative = None;
+        Ok(self.state.values())
+    }
+
+    pub(crate) fn fixed_tableau_step(
+        &mut self,
+        tableau: &ButcherTableau<Float>,
+    ) -> Result<Vector::Values, Vector::Failure> {
+        let (solution, _) = self.explicit_tableau(tableau)?;
+        self.time += self.dt;
+        self.state = solution;
+        self.derivative = None;
+        Ok(self.state.values())
+    }
+
+    pub fn dynamic_step(&mut self) -> Result<Vector::Values, SolverError<Float>> {
+        self.check_tolerances()?;
+        // first same as last, the final stage of the previous step is the first stage of this one
+        let derivative = self.current_derivative()?;
+        if !derivative.is_finite() {
+            return Err(SolverError::NonFiniteState { time: self.time });
+        }
+
+        self.adaptive_step(5, |integrator| {
+            let step = integrator.dormand_prince_45(derivative.clone())?;
+            Ok((step.solution, step.error, Some(step.derivative)))
+        })?;
+
+        Ok(self.state.values())
+    }
+
+    pub fn adaptive_tableau_step(
+        &mut self,
+        tableau: &ButcherTableau<Float>,
+    ) -> Result<Vector::Values, SolverError<Float>> {
+        self.check_tolerances()?;
+        self.adaptive_tableau(tableau)?;
+
+        Ok(self.state.values())
+    }
+
+    // variable step, variable order adams-bashforth-moulton, two evaluations per step once it is running
+    pub fn adams_step(&mut self) -> Result<Vector::Values, SolverError<Float>> {
+        self.check_tolerances()?;
+        // the history only describes the current trajectory while it ends where the integrator is
+        if self.history.last().is_none_or(|&(time, _)| time != self.time) {
+            self.history.clear();
+            self.adams_order = Self::ADAMS_STARTUP;
+            self.steps_at_order = 0;
+        }
+        let derivative = self.current_derivative()?;
+        if !derivative.is_finite() {
+            return Err(SolverError::NonFiniteState { time: self.time });
+        }
+        if self.history.is_empty() {
+            self.history.push((self.time, derivative));
+        }
+
+        // dormand prince steps under the same tolerances until there are enough derivatives to interpolate, so the
+        // start neither depends on the initial step size nor loses the accuracy asked for
+        if self.history.len() < Self::ADAMS_STARTUP {
+            self.dynamic_step()?;
+            let derivative = self.current_derivative()?;
+            self.history.push((self.time, derivative));
+            return Ok(self.state.values());
+        }
+
+        // variable step multistep formulas lose stability on sudden jumps in the step size
+        let last = self.history[self.history.len() - 1].0 - self.history[self.history.len() - 2].0;
+        let limit = last.abs() * Float::floatify(Self::ADAMS_GROWTH);
+        self.dt = self.dt.min(limit).max(-limit);
+
+        let order = self.adams_order;
+        let (start, previous) = (self.time, self.state.clone());
+        self.adaptive_step(order + 1, |integrator| integrator.adams_bashforth_moulton(order))?;
+        let derivative = self.current_derivative()?;
+        self.history.push((self.time, derivative));
+        if self.history.len() > Self::ADAMS_MAX_ORDER + 2 {
+            self.history.remove(0);
+        }
+        self.select_adams_order(self.time - start, &previous);
+
+        Ok(self.state.values())
+    }
+
+    // gragg-bulirsch-stoer, modified midpoint with 2, 4, 6, ... substeps extrapolated towards zero step size
+    pub fn extrapolation_step(&mut self) -> Result<Vector::Values, SolverError<Float>> {
+        self.check_tolerances()?;
+        let derivative = self.current_derivative()?;
+        if !derivative.is_finite() {
+            return Err(SolverError::NonFiniteState { time: self.time });
+        }
+        self.dt = self.control.limit_step(self.dt);
+
+        let mut rejected = false;
+        loop {
+            if self.dt.abs() < self.control.min_step || self.time + self.dt == self.time {
+                return Err(SolverError::StepSizeUnderflow { time: self.time, step: self.dt });
+            }
+
+            let last = (self.extrapolation_columns + 1).min(Self::EXTRAPOLATION_MAX_COLUMNS);
+            let mut row: Vec<Vector> = Vec::new();
+            // column, proposed step and work per unit step for every column with an error estimate
+            let mut proposals: Vec<(usize, Float, Float)> = Vec::new();
+            let mut work = Float::floatify(1.);
+            let mut accepted = None;
+            for column in 1..=last {
+                let substeps = 2 * column;
+                let mut next = vec![self.modified_midpoint(&derivative, substeps)?];
+                work += Float::floatify((substeps - 1) as f64);
+
+                // aitken-neville, the error expansion of the midpoint rule only has even powers
+                (1..column).for_each(|level| {
+                    let ratio =
+                        Float::floatify((substeps * substeps) as f64 / (4 * (column - level).pow(2)) as f64);
+                    let difference =
+                        Vector::combine(&next[level - 1], &[(&row[level - 1], Float::floatify(-1.))]);
+                    let weight = Float::floatify(1.) / (ratio - Float::floatify(1.));
+                    next.push(Vector::combine(&next[level - 1], &[(&difference, weight)]));
+                });
+
+                if column >= 2 {
+                    let difference =
+                        Vector::combine(&next[column - 1], &[(&next[column - 2], Float::floatify(-1.))]);
+                    let error = self.control.error_norm(&difference, &self.state, &next[column - 1]);
+                    let (converged, step) = self.control.propose(self.dt, error, 2 * column - 1, rejected);
+                    proposals.push((column, step, work / step.abs()));
+                    if converged {
+                        accepted = Some((next[column - 1].clone(), column));
+                    }
+                }
+                row = next;
+                if accepted.is_some() {
+                    break;
+                }
+            }
+
+            // carry on with whichever column reaches the furthest per evaluation
+            let cheapest = proposals
+                .iter()
+                .min_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(std::cmp::Ordering::Equal))
+                .copied();
+            let Some((mut columns, mut step, _)) = cheapest else {
+                return Err(SolverError::StepSizeUnderflow { time: self.time, step: self.dt });
+            };
+
+            let Some((solution, column)) = accepted else {
+                rejected = true;
+                self.extrapolation_columns = columns.max(2);
+                self.dt = step;
+                continue;
+            };
+
+            // the best column was the last one tried, so one more column may pay off next step
+            if columns == column && column < Self::EXTRAPOLATION_MAX_COLUMNS && !rejected {
+                let extra = Float::floatify((2 * (column + 1) - 1) as f64);
+                step = step * (work + extra) / work;
+                columns += 1;
+            }
+
+            self.time += self.dt;
+            self.state = solution;
+            self.derivative = None;
+            self.extrapolation_columns = columns.max(2);
+            self.dt = self.control.limit_step(step);
+
+            return Ok(self.state.values());
+        }
+    }
+
+    fn modified_midpoint(
+        &mut self,
+        derivative: &Vector,
+        substeps: usize,
+    ) -> Result<Vector, SolverError<Float>> {
+        let step = self.dt / Float::floatify(substeps as f64);
+        let mut previous = self.state.clone();
+        let mut current = Vector::combine(&self.state, &[(derivative, step)]);
+        for idx in 1..substeps {
+            let slope = self.slope(step * Float::floatify(idx as f64), &current)?;
+            let next = Vector::combine(&previous, &[(&slope, step * Float::floatify(2.))]);
+            previous = current;
+            current = next;
+        }
+
+        Ok(current)
+    }
+
+    // predicts from the past derivatives, then corrects one order higher with the derivative at the prediction
+    fn adams_bashforth_moulton(&mut self, order: usize) -> Result<AdamsStep<Vector>, SolverError<Float>> {
+        let one = Float::floatify(1.);
+        let past = self.history[self.history.len() - order..].to_vec();
+        let predicted =
+            Vector::combine(&self.state, &[(&Self::adams_increment(self.time, self.dt, &past), one)]);
+
+        let target = (self.time + self.dt, self.slope(self.dt, &predicted)?);
+        let mut points = past;
+        points.push(target);
+        let corrected =
+            Vector::combine(&self.state, &[(&Self::adams_increment(self.time, self.dt, &points), one)]);
+
+        // the final evaluation feeds both the next step and the history
+        let derivative = self.slope(self.dt, &corrected)?;
+        let error = Vector::combine(&corrected, &[(&predicted, Float::floatify(-1.))]);
+
+        Ok((corrected, error, Some(derivative)))
+    }
+
+    // integral from start to start + step of the polynomial interpolating the derivatives at the given times
+    fn adams_increment(start: Float, step: Float, points: &[(Float, Vector)]) -> Vector {
+        let nodes: Vec<Float> = points.iter().map(|&(time, _)| (time - start) / step).collect();
+        let terms: Vec<(&Vector, Float)> = (0..nodes.len())
+            .map(|idx| {
+                // expand the lagrange basis polynomial in powers of the scaled time, then integrate over [0, 1]
+                let mut coefficients = vec![Float::floatify(1.)];
+                (0..nodes.len()).filter(|&other| other != idx).for_each(|other| {
+                    let scale = Float::floatify(1.) / (nodes[idx] - nodes[other]);
+                    let mut product = vec![Float::default(); coefficients.len() + 1];
+                    coefficients.iter().enumerate().for_each(|(power, &coefficient)| {
+                        product[power + 1] += coefficient * scale;
+                        product[power] -= coefficient * nodes[other] * scale;
+                    });
+                    coefficients = product;
+                });
+                let weight =
+                    coefficients.iter().enumerate().fold(Float::default(), |sum, (power, &coefficient)| {
+                        sum + coefficient / Float::floatify((power + 1) as f64)
+                    });
+
+                (&points[idx].1, weight * step)
+            })
+            .collect();
+
+        Vector::combine(&points[0].1.zeroed(), &terms)
+    }
+
+    // compares predictor and corrector of the neighbouring orders over the step just taken
+    fn select_adams_order(&mut self, taken: Float, previous: &Vector) {
+        self.steps_at_order += 1;
+        if self.steps_at_order <= self.adams_order {
+            return;
+        }
+
+        let available = self.history.len() - 1;
+        let start = self.history[available - 1].0;
+        let estimate = |integrator: &Self, order: usize, bias: f64| -> Option<(Float, Float)> {
+            if order == 0 || order > Self::ADAMS_MAX_ORDER || order > available {
+                return None;
+            }
+            let past = &integrator.history[available - order..available];
+            let points = &integrator.history[available - order..];
+            let difference = Vector::combine(
+                &Self::adams_increment(start, taken, points),
+                &[(&Self::adams_increment(start, taken, past), Float::floatify(-1.))],
+            );
+            let error = integrator.control.error_norm(&difference, previous, &integrator.state);
+            let exponent = Float::floatify(1. / (order + 1) as f64);
+            let ratio = (Float::floatify(1.) / error.max(Float::epsilon())).powf(exponent);
+            Some((ratio / Float::floatify(bias), error))
+        };
+
+        let order = self.adams_order;
+        let candidates = [(order, estimate(self, order, 1.2)), (order - 1, estimate(self, order - 1, 1.3))];
+        let candidates = candidates.into_iter().chain([(order + 1, estimate(self, order + 1, 1.4))]);
+        let best = candidates
+            .filter_map(|(order, estimate)| estimate.map(|estimate| (order, estimate)))
+            .max_by(|a, b| a.1 .0.partial_cmp(&b.1 .0).unwrap_or(std::cmp::Ordering::Equal));
+
+        if let Some((best, (_, error))) = best
+            && best != order
+        {
+            self.adams_order = best;
+            self.steps_at_order = 0;
+            self.dt = self.control.propose(taken, error, best + 1, false).1;
+        }
+    }
+
+    pub fn solve_until(&mut self, final_time: Float) -> Result<Vec<Vector::Values>, SolverError<Float>> {
+        self.solve_by(final_time, |integrator| Ok(integrator.fixed_step()?), Self::record_state)
+    }
+
+    pub fn solve_with_time(
+        &mut self,
+        final_time: Float,
+    ) -> Result<Vec<(Float, Vector::Values)>, SolverError<Float>> {
+        self.solve_by(final_time, |integrator| Ok(integrator.fixed_step()?), Self::record_with_time)
+    }
+
+    pub fn solve_dense(
+        &mut self,
+        final_time: Float,
+    ) -> Result<StateSolution<Float, Vector>, SolverError<Float>> {
+        self.solve_dense_by(final_time, |integrator| Ok(integrator.fixed_step()?))
+    }
+
+    pub fn solve_dynamic_until(
+        &mut self,
+        final_time: Float,
+    ) -> Result<Vec<Vector::Values>, SolverError<Float>> {
+        self.solve_by(final_time, Self::dynamic_step, Self::record_state)
+    }
+
+    pub fn solve_dynamic_with_time(
+        &mut self,
+        final_time: Float,
+    ) -> Result<Vec<(Float, Vector::Values)>, SolverError<Float>> {
+        self.solve_by(final_time, Self::dynamic_step, Self::record_with_time)
+    }
+
+    pub fn solve_dynamic_dense(
+        &mut self,
+        final_time: Float,
+    ) -> Result<StateSolution<Float, Vector>, SolverError<Float>> {
+        self.solve_dense_by(final_time, Self::dynamic_step)
+    }
+
+    pub fn solve_adams_until(
+        &mut self,
+        final_time: Float,
+    ) -> Result<Vec<Vector::Values>, SolverError<Float>> {
+        self.solve_by(final_time, Self::adams_step, Self::record_state)
+    }
+
+    pub fn solve_adams_with_time(
+        &mut self,
+        final_time: Float,
+    ) -> Result<Vec<(Float, Vector::Values)>, SolverError<Float>> {
+        self.solve_by(final_time, Self::adams_step, Self::record_with_time)
+    }
+
+    pub fn solve_adams_dense(
+        &mut self,
+        final_time: Float,
+    ) -> Result<StateSolution<Float, Vector>, SolverError<Float>> {
+        self.solve_dense_by(final_time, Self::adams_step)
+    }
+
+    pub fn solve_extrapolation_until(
+        &mut self,
+        final_time: Float,
+    ) -> Result<Vec<Vector::Values>, SolverError<Float>> {
+        self.solve_by(final_time, Self::extrapolation_step, Self::record_state)
+    }
+
+    pub fn solve_extrapolation_with_time(
+        &mut self,
+        final_time: Float,
+    ) -> Result<Vec<(Float, Vector::Values)>, SolverError<Float>> {
+        self.solve_by(final_time, Self::extrapolation_step, Self::record_with_time)
+    }
+
+    pub fn solve_extrapolation_dense(
+        &mut self,
+        final_time: Float,
+    ) -> Result<StateSolution<Float, Vector>, SolverError<Float>> {
+        self.solve_dense_by(final_time, Self::extrapolation_step)
+    }
+
+    // dynamic steps that land exactly on every requested time, which has to run away from the current time in one
+    // direction, a terminal event stops the output at the last time reached before it
+    pub fn solve_at(&mut self, times: &[Float]) -> Result<Vec<(Float, Vector::Values)>, SolverError<Float>> {
+        check_output_times(self.time, times)?;
+
+        let mut output = Vec::with_capacity(times.len());
+        for &time in times {
+            self.solve_by(time, Self::dynamic_step, |_| Ok(()))?;
+            if self.time != time {
+                break;
+            }
+            output.push((time, self.state.values()));
+        }
+
+        Ok(output)
+    }
+
+    pub fn solve_tableau_until(
+        &mut self,
+        final_time: Float,
+        tableau: &ButcherTableau<Float>,
+    ) -> Result<Vec<Vector::Values>, SolverError<Float>> {
+        self.solve_by(final_time, |integrator| integrator.adaptive_tableau_step(tableau), Self::record_state)
+    }
+
+    pub fn solve_tableau_with_time(
+        &mut self,
+        final_time: Float,
+        tableau: &ButcherTableau<Float>,
+    ) -> Result<Vec<(Float, Vector::Values)>, SolverError<Float>> {
+        self.solve_by(
+            final_time,
+            |integrator| integrator.adaptive_tableau_step(tableau),
+            Self::record_with_time,
+        )
+    }
+
+    // every crossing of the section surface up to the final time, located on the interpolant of each dynamic
+    // step, events added beforehand still fire alongside it and a terminal one ends the solve early
+    pub fn solve_dynamic_section(
+        &mut self,
+        final_time: Float,
+        section: StateEvent<Float, Vector::Slice>,
+    ) -> Result<Vec<(Float, Vector::Values)>, SolverError<Float>> {
+        let (index, logged) = (self.events.len(), self.event_log.len());
+        self.events.push(section);
+        let solved = self.solve_by(final_time, Self::dynamic_step, |_| Ok(()));
+        self.events.pop();
+
+        // the section's own crossings are handed back rather than left in the event log
+        let (crossings, others): (Vec<_>, Vec<_>) =
+            self.event_log.drain(logged..).partition(|record| record.index == index);
+        self.event_log.extend(others);
+        solved?;
+
+        Ok(crossings.into_iter().map(|record| (record.time, record.state)).collect())
+    }
+
+    // runtime sized states can change length after their tolerances were set
+    fn check_tolerances(&self) -> Result<(), SolverError<Float>> {
+        let expected = self.state.components().len();
+        match [self.control.absolute.dimension(), self.control.relative.dimension()]
+            .into_iter()
+            .find(|&len| len != expected)
+        {
+            Some(found) => Err(SolverError::DimensionMismatch { time: self.time, expected, found }),
+            None => Ok(()),
+        }
+    }
+
+    fn solve_dense_by(
+        &mut self,
+        final_time: Float,
+        advance: impl FnMut(&mut Self) -> Result<Vector::Values, SolverError<Float>>,
+    ) -> Result<StateSolution<Float, Vector>, SolverError<Float>> {
+        let derivative = self.current_derivative()?;
+        let mut solution = StateSolution::build(self.time, self.state.values(), derivative.values());
+        solution.extend(self.solve_by(final_time, advance, Self::record_dense)?);
+
+        Ok(solution)
+    }
+
+    // an evaluation failing while events are located or the step is recorded ends the solve with its error
+    fn solve_by<Output>(
+        &mut self,
+        final_time: Float,
+        mut advance: impl FnMut(&mut Self) -> Result<Vector::Values, SolverError<Float>>,
+        mut record: impl FnMut(&mut Self) -> Result<Output, SolverError<Float>>,
+    ) -> Result<Vec<Output>, SolverError<Float>> {
+        let advance = |integrator: &mut Self| {
+            let start = integrator.segment_start()?;
+            advance(integrator).map(|_| start)
+        };
+
+        let output = solve_clamped(self, final_time, advance, |integrator, start| {
+            let terminated = match start {
+                Some(start) => integrator.detect_events(start),
+                None => Ok(false),
+            };
+            match terminated.and_then(|terminated| Ok((terminated, record(integrator)?))) {
+                Ok((false, recorded)) => ControlFlow::Continue(Ok(recorded)),
+                Ok((true, recorded)) => ControlFlow::Break(Ok(recorded)),
+                Err(error) => ControlFlow::Break(Err(error)),
+            }
+        })?;
+
+        output.into_iter().collect()
+    }
+
+    fn record_state(&mut self) -> Result<Vector::Values, SolverError<Float>> {
+        Ok(self.state.values())
+    }
+
+    fn record_with_time(&mut self) -> Result<(Float, Vector::Values), SolverError<Float>> {
+        Ok((self.time, self.state.values()))
+    }
+
+    fn record_dense(&mut self) -> Result<(Float, Vector::Values, Vector::Values), SolverError<Float>> {
+        Ok((self.time, self.state.values(), self.current_derivative()?.values()))
     }
 
-    pub fn dynamic_step(&mut self) -> [Float; N] {
-        let mut oracle = *self;
-        oracle.dt = self.dt * Float::floatify(0.5);
-        (0..2).for_each(|_| {
-            oracle.step();
+    fn segment_start(&mut self) -> Result<Option<SegmentEnd<Float, Vector>>, SolverError<Float>> {
+        if self.events.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some((self.time, self.state.clone(), self.current_derivative()?)))
+    }
+
+    fn current_derivative(&mut self) -> Result<Vector, SolverError<Float>> {
+        let derivative = match self.derivative.take() {
+            Some(derivative) => derivative,
+            None => Vector::dynamics(&mut self.system, self.time, &self.state)?,
+        };
+        self.derivative = Some(derivative.clone());
+
+        Ok(derivative)
+    }
+
+    // returns true when a terminal event cut the step short, the integrator is then left at the event
+    fn detect_events(&mut self, start: SegmentEnd<Float, Vector>) -> Result<bool, SolverError<Float>> {
+        let end = (self.time, self.state.clone(), self.current_derivative()?);
+        let segment = HermiteSegment::build(start, end);
+
+        let mut crossings = Vec::new();
+        self.events.iter_mut().enumerate().for_each(|(index, event)| {
+            let before = event.evaluate(segment.t0, segment.y0.slice());
+            let after = event.evaluate(segment.t1, segment.y1.slice());
+            if event.crossed(before, after) {
+                let time = event.locate(&segment, before, after);
+                crossings.push(StateEventRecord { index, time, state: segment.evaluate(time) });
+            }
+        });
+        // order crossings along the direction of integration, which is backwards for negative steps
+        crossings.sort_by(|a, b| {
+            let order = a.time.partial_cmp(&b.time).unwrap_or(std::cmp::Ordering::Equal);
+            if self.dt < Float::default() {
+                order.reverse()
+            } else {
+                order
+            }
         });
-        let step = State::build(self.runge_kutta_4());
 
-        let error = (step * Floating::floatify(-1.) + oracle.state).norm();
-        if error > Floating::floatify(Self::TOLERANCE) {
-            self.dt *= Floating::floatify(0.5);
-            return self.dynamic_step();
+        let terminal = crossings.iter().position(|record| self.events[record.index].terminal);
+        if let Some(position) = terminal {
+            crossings.truncate(position + 1);
+            self.time = crossings[position].time;
+            self.state = Vector::from_values(crossings[position].state.clone());
+            self.derivative = None;
         }
+        self.event_log.extend(crossings);
 
-        self.time += self.dt;
-        self.state = step;
-        self.dt *= Floating::floatify(2.);
+        Ok(terminal.is_some())
+    }
+}
+
+impl<Float, Vector, System> ClampedSolve<Float> for OdeIntegrator<Float, Vector, System>
+where
+    Float: Floating,
+    Vector: StepState<Float>,
+{
+    fn time(&mut self) -> &mut Float {
+        &mut self.time
+    }
+
+    fn step_size(&mut self) -> &mut Float {
+        &mut self.dt
+    }
+
+    fn max_steps(&self) -> usize {
+        self.control.max_steps
+    }
+
+    fn min_step(&mut self) -> &mut Float {
+        &mut self.control.min_step
+    }
+
+    fn is_finite(&self) -> bool {
+        self.state.is_finite()
+    }
+
+    // the adams history keeps describing the trajectory when its last entry moves with the time
+    fn landed(&mut self, landing: Float, final_time: Float) {
+        if let Some(last) = self.history.last_mut()
+            && last.0 == landing
+        {
+            last.0 = final_time;
+        }
+    }
+}
+
+impl<Float, Vector, System> AdaptiveStep<Float, Vector::Tolerance> for OdeIntegrator<Float, Vector, System>
+where
+    Float: Floating,
+    Vector: StepState<Float>,
+{
+    fn control(&self) -> &Control<Float, Vector::Tolerance> {
+        &self.control
+    }
+}
+
+impl<Float, Vector, System> IntegrationStep<Float, Vector> for OdeIntegrator<Float, Vector, System>
+where
+    Float: Floating,
+    Vector: Evaluate<Float, System>,
+{
+    type Failure = Vector::Failure;
+
+    fn current(&self) -> &Vector {
+        &self.state
+    }
+
+    fn slope(&mut self, offset: Float, state: &Vector) -> Result<Vector, Vector::Failure> {
+        Vector::dynamics(&mut self.system, self.time + offset, state)
+    }
+
+    fn accept(&mut self, solution: Vector, derivative: Option<Vector>) {
+        self.state = solution;
+        self.derivative = derivative;
+    }
+}
+
+// delay differential equations, the past is kept as a dense solution so stages can look back into it
+pub struct DelayIntegrator<Float, const N: usize, System, History> {
+    state: State<Float, N>,
+    dt: Float,
+    system: System,
+    history: History,
+    time: Float,
+    delays: Vec<Float>,
+    derivative: Option<State<Float, N>>,
+    control: StepControl<Float, N>,
+    past: Option<Solution<Float, N>>,
+    breakpoints: Vec<Float>,
+    // shared so a step can hold on to them while it moves the integrator
+    rk4: Rc<ButcherTableau<Float>>,
+    bogacki_shampine: Rc<ButcherTableau<Float>>,
+}
+
+impl<Float, const N: usize, Dynamics, History> DelayIntegrator<Float, N, Autonomous<Dynamics>, History>
+where
+    Float: Floating,
+    Dynamics: FnMut(&[Float; N], &[[Float; N]]) -> [Float; N],
+    History: FnMut(Float) -> [Float; N],
+{
+    pub fn build(
+        state: [Float; N],
+        delta_time: Float,
+        delays: &[Float],
+        history: History,
+        dynamics: Dynamics,
+    ) -> Result<Self, SolverError<Float>> {
+        Self::build_system(state, delta_time, delays, history, Autonomous(dynamics))
+    }
+}
+
+impl<Float, const N: usize, Dynamics, History> DelayIntegrator<Float, N, Dynamics, History>
+where
+    Float: Floating,
+    Dynamics: FnMut(Float, &[Float; N], &[[Float; N]]) -> [Float; N],
+    History: FnMut(Float) -> [Float; N],
+{
+    pub fn build_driven(
+        state: [Float; N],
+        delta_time: Float,
+        delays: &[Float],
+        history: History,
+        dynamics: Dynamics,
+    ) -> Result<Self, SolverError<Float>> {
+        Self::build_system(state, delta_time, delays, history, dynamics)
+    }
+}
+
+impl<Float, const N: usize, System, History> DelayIntegrator<Float, N, System, History>
+where
+    Float: Floating,
+    System: DelaySystem<Float, N>,
+    History: FnMut(Float) -> [Float; N],
+{
+    // a jump in the derivative at the start is smoothed out once per delay, past this many levels it is
+    // below what the steppers and the cubic past can resolve
+    const DISCONTINUITY_LEVELS: usize = 4;
+
+    // delays must be positive and finite, the history covers every time before the start
+    pub fn build_system(
+        state: [Float; N],
+        delta_time: Float,
+        delays: &[Float],
+        history: History,
+        system: System,
+    ) -> Result<Self, SolverError<Float>> {
+        if let Some(&delay) = delays.iter().find(|&&delay| !(delay.is_finite() && delay > Float::default())) {
+            return Err(SolverError::InvalidDelay { delay });
+        }
+
+        Ok(DelayIntegrator {
+            state: State::build(state),
+            dt: delta_time,
+            system,
+            history,
+            time: Float::default(),
+            delays: delays.to_vec(),
+            derivative: None,
+            control: StepControl::build(),
+            past: None,
+            breakpoints: Vec::new(),
+            rk4: Rc::new(ButcherTableau::rk4()),
+            bogacki_shampine: Rc::new(ButcherTableau::bogacki_shampine()),
+        })
+    }
+
+    pub const fn state(&self) -> [Float; N] {
+        self.state.values()
+    }
+
+    pub const fn delta_time(&self) -> Float {
+        self.dt
+    }
+
+    pub const fn curr_time(&self) -> Float {
+        self.time
+    }
+
+    pub fn delays(&self) -> &[Float] {
+        &self.delays
+    }
+
+    // derivative discontinuities still ahead, propagated from the start time through every delay
+    pub fn breakpoints(&self) -> &[Float] {
+        &self.breakpoints
+    }
+
+    // everything integrated since the start, the history function is not part of it
+    pub fn past(&self) -> Option<&Solution<Float, N>> {
+        self.past.as_ref()
+    }
+
+    // moves the start of the problem, the stored past is discarded
+    pub fn set_time(&mut self, time: Float) -> &mut Self {
+        self.time = time;
+        self.restart();
+        self
+    }
+
+    pub fn set_state(&mut self, state: [Float; N]) -> &mut Self {
+        self.state = State::build(state);
+        self.restart();
+        self
+    }
+
+    pub fn system(&mut self) -> &mut System {
+        &mut self.system
+    }
+
+    pub fn set_control(&mut self) -> &mut StepControl<Float, N> {
+        &mut self.control
+    }
+
+    pub fn step(&mut self) -> [Float; N] {
+        let tableau = Rc::clone(&self.rk4);
+        let (derivative, target, longest) = self.prepare();
+        let stored = self.dt;
+        self.dt = self.dt.min(longest).min(target - self.time);
+
+        let (solution, _, _) = self.delayed_tableau(&tableau, derivative);
+        self.accept(solution, None, target);
+        self.dt = stored;
 
         self.state()
     }
 
-    pub fn solve_until(&mut self, final_time: Float) -> Vec<[Float; N]> {
-        let mut states = Vec::new();
-        while self.time < final_time {
-            states.push(self.step());
+    // bogacki-shampine like dde23, its third order matches the cubic interpolation of the stored past
+    pub fn dynamic_step(&mut self) -> Result<[Float; N], SolverError<Float>> {
+        let tableau = Rc::clone(&self.bogacki_shampine);
+        let (derivative, target, longest) = self.prepare();
+        if !derivative.is_finite() {
+            return Err(SolverError::NonFiniteState { time: self.time });
         }
 
-        states
+        let mut rejected = false;
+        loop {
+            // stop at the next discontinuity instead of stepping across it
+            let stored = self.control.limit_step(self.dt);
+            self.dt = stored.min(longest).min(target - self.time);
+            let limited = self.dt < stored;
+            if self.dt < self.control.min_step || self.time + self.dt == self.time {
+                return Err(SolverError::StepSizeUnderflow { time: self.time, step: self.dt });
+            }
+
+            let (solution, error, last) = self.delayed_tableau(&tableau, derivative);
+            let error = error.unwrap_or(State::build([Float::default(); N]));
+            let error = self.control.error_norm(&error, &self.state, &solution);
+            let (accepted, delta_time) = self.control.propose(self.dt, error, tableau.order, rejected);
+
+            if accepted {
+                self.accept(solution, Some(last), target);
+                // a step cut short by a breakpoint or the shortest delay says little about the next one
+                self.dt = if limited { stored.max(delta_time) } else { delta_time };
+
+                return Ok(self.state());
+            }
+
+            rejected = true;
+            self.dt = delta_time;
+        }
+    }
+
+    pub fn solve_until(&mut self, final_time: Float) -> Result<Vec<[Float; N]>, SolverError<Float>> {
+        Ok(self.solve_with_time(final_time)?.into_iter().map(|(_, state)| state).collect())
+    }
+
+    pub fn solve_with_time(
+        &mut self,
+        final_time: Float,
+    ) -> Result<Vec<(Float, [Float; N])>, SolverError<Float>> {
+        self.solve_by(final_time, |integrator| Ok(integrator.step()))
+    }
+
+    pub fn solve_dynamic_until(&mut self, final_time: Float) -> Result<Vec<[Float; N]>, SolverError<Float>> {
+        Ok(self.solve_dynamic_with_time(final_time)?.into_iter().map(|(_, state)| state).collect())
     }
 
-    pub fn solve_with_time(&mut self, final_time: Float) -> Vec<(Float, [Float; N])> {
-        let mut output = Vec::new();
-        while self.time < final_time {
-            output.push((self.time, self.step()));
+    pub fn solve_dynamic_with_time(
+        &mut self,
+        final_time: Float,
+    ) -> Result<Vec<(Float, [Float; N])>, SolverError<Float>> {
+        self.solve_by(final_time, Self::dynamic_step)
+    }
+
+    pub fn solve_dynamic_dense(
+        &mut self,
+        final_time: Float,
+    ) -> Result<Solution<Float, N>, SolverError<Float>> {
+        self.solve_dynamic_with_time(final_time)?;
+        let (state, derivative) = (self.state(), self.current_derivative().values());
+        Ok(self.past.clone().unwrap_or_else(|| Solution::build(self.time, state, derivative)))
+    }
+
+    // the delayed state for any time, from the history before the start and the dense past after it
+    pub fn lookup(&mut self, time: Float) -> [Float; N] {
+        match &self.past {
+            Some(past) if time >= past.start_time() => past.evaluate(time).unwrap_or(self.state.values()),
+            _ => (self.history)(time),
+        }
+    }
+
+    // delays only look backwards, so integration always runs forward in time
+    fn solve_by(
+        &mut self,
+        final_time: Float,
+        advance: impl FnMut(&mut Self) -> Result<[Float; N], SolverError<Float>>,
+    ) -> Result<Vec<(Float, [Float; N])>, SolverError<Float>> {
+        if final_time < self.time {
+            return Err(SolverError::BackwardIntegration { time: self.time, final_time });
         }
 
-        output
+        solve_clamped(self, final_time, advance, |integrator, state| {
+            ControlFlow::Continue((integrator.time, state))
+        })
     }
 
-    pub fn solve_dynamic_until(&mut self, final_time: Float) -> Vec<[Float; N]> {
-        let mut states = Vec::new();
-        while self.time < final_time {
-            states.push(self.dynamic_step());
+    fn restart(&mut self) {
+        self.derivative = None;
+        self.past = None;
+        self.breakpoints.clear();
+    }
+
+    // starts the dense past on the first step, then returns the first stage, the next stopping time and the
+    // longest step allowed
+    fn prepare(&mut self) -> (State<Float, N>, Float, Float) {
+        let derivative = self.current_derivative();
+        if self.past.is_none() {
+            self.past = Some(Solution::build(self.time, self.state(), derivative.values()));
+            self.breakpoints = self.discontinuities();
         }
 
-        states
+        // stages never reach past the stored solution when the step stays below the shortest delay
+        let shortest =
+            self.delays.iter().fold(Float::floatify(f64::INFINITY), |shortest, &delay| shortest.min(delay));
+        self.breakpoints.retain(|&breakpoint| breakpoint > self.time);
+        let target = self.breakpoints.first().copied().unwrap_or(Float::floatify(f64::INFINITY));
+
+        (derivative, target, shortest)
     }
 
-    pub fn solve_dynamic_with_time(&mut self, final_time: Float) -> Vec<(Float, [Float; N])> {
-        let mut output = Vec::new();
-        while self.time < final_time {
-            output.push((self.time, self.dynamic_step()));
+    fn accept(&mut self, solution: State<Float, N>, derivative: Option<State<Float, N>>, target: Float) {
+        let landed = self.time + self.dt >= target;
+        self.time = if landed { target } else { self.time + self.dt };
+        self.state = solution;
+
+        // the derivative may jump at a breakpoint, so the next step reevaluates its first stage there
+        self.derivative = if landed { None } else { derivative };
+        let derivative = self.current_derivative();
+        if let Some(past) = self.past.as_mut() {
+            past.push(self.time, self.state.values(), derivative.values());
         }
+    }
 
-        output
+    fn current_derivative(&mut self) -> State<Float, N> {
+        let derivative = match self.derivative {
+            Some(derivative) => derivative,
+            None => self.evaluate(Float::default(), self.state),
+        };
+        self.derivative = Some(derivative);
+        derivative
+    }
+
+    fn evaluate(&mut self, offset: Float, state: State<Float, N>) -> State<Float, N> {
+        let time = self.time + offset;
+        let delayed: Vec<[Float; N]> =
+            (0..self.delays.len()).map(|idx| self.lookup(time - self.delays[idx])).collect();
+        State::build(self.system.dynamics(time, &state.inner, &delayed))
+    }
+
+    fn delayed_tableau(
+        &mut self,
+        tableau: &ButcherTableau<Float>,
+        derivative: State<Float, N>,
+    ) -> (State<Float, N>, Option<State<Float, N>>, State<Float, N>) {
+        let mut stages: Vec<State<Float, N>> = vec![derivative];
+        (1..tableau.stages()).for_each(|stage| {
+            let intermediate = (0..stage).fold(self.state, |intermediate, prev| {
+                intermediate + stages[prev] * (self.dt * tableau.a[stage][prev])
+            });
+            stages.push(self.evaluate(self.dt * tableau.c[stage], intermediate));
+        });
+
+        let weighted = |weights: &[Float]| {
+            (0..tableau.stages()).fold(State::build([Float::default(); N]), |sum, stage| {
+                sum + stages[stage] * (self.dt * weights[stage])
+            })
+        };
+        let solution = self.state + weighted(&tableau.b);
+        let error = tableau.b_hat.as_ref().map(|b_hat| {
+            let weights: Vec<Float> = tableau.b.iter().zip(b_hat).map(|(&b, &b_hat)| b - b_hat).collect();
+            weighted(&weights)
+        });
+
+        (solution, error, stages[stages.len() - 1])
+    }
+
+    // every sum of up to DISCONTINUITY_LEVELS delays, measured from the start
+    fn discontinuities(&self) -> Vec<Float> {
+        let close = |a: Float, b: Float| {
+            (a - b).abs() <= Float::epsilon() * a.abs().max(Float::floatify(1.)) * Float::floatify(16.)
+        };
+        let sorted = |mut points: Vec<Float>| {
+            points.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            points.dedup_by(|a, b| close(*a, *b));
+            points
+        };
+
+        let mut level = vec![self.time];
+        let mut breakpoints = Vec::new();
+        for _ in 0..Self::DISCONTINUITY_LEVELS {
+            level = sorted(
+                level.iter().flat_map(|&point| self.delays.iter().map(move |&delay| point + delay)).collect(),
+            );
+            breakpoints.extend(level.iter().copied());
+        }
+
+        sorted(breakpoints)
     }
 }
 
-impl<Float, const N: usize> IntegrationStep<[Float; N]> for Integrator<Float, N>
+impl<Float, const N: usize, System, History> ClampedSolve<Float>
+    for DelayIntegrator<Float, N, System, History>
 where
-    Float: Floating + Default + Copy,
+    Float: Floating,
 {
-    fn runge_kutta_4(&self) -> [Float; N] {
-        let k1 = State::build((self.ddt)(&self.state.inner));
-        let k2 = State::build((self.ddt)(&(self.state + k1 * (self.dt / Float::floatify(2.))).inner));
-        let k3 = State::build((self.ddt)(&(self.state + k2 * (self.dt / Float::floatify(2.))).inner));
-        let k4 = State::build((self.ddt)(&(self.state + k3 * self.dt).inner));
-
-        (self.state
-            + (k1 + k4) * (self.dt / Float::floatify(6.))
-            + (k2 + k3) * (self.dt / Float::floatify(3.)))
-        .values()
+    fn time(&mut self) -> &mut Float {
+        &mut self.time
+    }
+
+    fn step_size(&mut self) -> &mut Float {
+        &mut self.dt
+    }
+
+    fn max_steps(&self) -> usize {
+        self.control.max_steps
+    }
+
+    fn min_step(&mut self) -> &mut Float {
+        &mut self.control.min_step
+    }
+
+    fn is_finite(&self) -> bool {
+        self.state.is_finite()
+    }
+
+    fn landed(&mut self, _landing: Float, final_time: Float) {
+        if let Some(past) = self.past.as_mut() {
+            past.retime_last(final_time);
+        }
     }
 }
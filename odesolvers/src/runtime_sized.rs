@@ -0,0 +1,70 @@
+use crate::butcher::ButcherTableau;
+use crate::error::SolverError;
+use crate::integration_shared::IntegrationStep;
+use crate::integration_shared::RuntimeState;
+use crate::runge_kutta::OdeIntegrator;
+use crate::scalar::Floating;
+use crate::system::Autonomous;
+use crate::system::RuntimeSystem;
+
+// the same integrator as Integrator for states whose dimension is chosen at runtime, every solve, event and
+// dense output works alike, states and events see slices and vectors instead of arrays
+pub type RuntimeIntegrator<Float, System> = OdeIntegrator<Float, RuntimeState<Float>, System>;
+
+impl<Float, Dynamics> RuntimeIntegrator<Float, Autonomous<Dynamics>>
+where
+    Float: Floating,
+    Dynamics: FnMut(&[Float]) -> Vec<Float>,
+{
+    pub fn build(state: Vec<Float>, delta_time: Float, dynamics: Dynamics) -> Self {
+        Self::build_system(state, delta_time, Autonomous(dynamics))
+    }
+}
+
+impl<Float, Dynamics> RuntimeIntegrator<Float, Dynamics>
+where
+    Float: Floating,
+    Dynamics: FnMut(Float, &[Float]) -> Vec<Float>,
+{
+    pub fn build_driven(state: Vec<Float>, delta_time: Float, dynamics: Dynamics) -> Self {
+        Self::build_system(state, delta_time, dynamics)
+    }
+}
+
+impl<Float, System> RuntimeIntegrator<Float, System>
+where
+    Float: Floating,
+    System: RuntimeSystem<Float>,
+{
+    pub fn build_system(state: Vec<Float>, delta_time: Float, system: System) -> Self {
+        Self::build_state(RuntimeState::build(state), delta_time, system)
+    }
+
+    pub fn state(&self) -> &[Float] {
+        &self.current().inner
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.current().inner.len()
+    }
+
+    // the tolerances follow a change of dimension
+    pub fn set_state(&mut self, state: Vec<Float>) -> &mut Self {
+        if state.len() != self.dimension() {
+            self.set_control().resize(state.len());
+        }
+        self.replace_state(RuntimeState::build(state));
+        self
+    }
+
+    pub fn step(&mut self) -> Result<Vec<Float>, SolverError<Float>> {
+        self.fixed_step()
+    }
+
+    pub fn tableau_step(
+        &mut self,
+        tableau: &ButcherTableau<Float>,
+    ) -> Result<Vec<Float>, SolverError<Float>> {
+        self.fixed_tableau_step(tableau)
+    }
+}
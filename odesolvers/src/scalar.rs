@@ -6,6 +6,7 @@ use std::ops::Div;
 use std::ops::DivAssign;
 use std::ops::Mul;
 use std::ops::MulAssign;
+use std::ops::Neg;
 use std::ops::Sub;
 use std::ops::SubAssign;
 
@@ -15,6 +16,7 @@ where
         + Sub<Self, Output = Self>
         + Mul<Self, Output = Self>
         + Div<Self, Output = Self>
+        + Neg<Output = Self>
         + AddAssign
         + SubAssign
         + MulAssign
@@ -26,9 +28,27 @@ where
 {
     fn floatify(value: f64) -> Self;
 
+    fn epsilon() -> Self;
+
     fn to_f32(self) -> f32;
 
     fn to_f64(self) -> f64;
+
+    fn abs(self) -> Self;
+
+    fn signum(self) -> Self;
+
+    fn is_finite(self) -> bool;
+
+    fn sqrt(self) -> Self;
+
+    fn powf(self, exponent: Self) -> Self;
+
+    fn ln(self) -> Self;
+
+    fn min(self, other: Self) -> Self;
+
+    fn max(self, other: Self) -> Self;
 }
 
 #[cfg(FALSE)]
@@ -37,6 +57,10 @@ impl Floating for f16 {
         value as f16
     }
 
+    fn epsilon() -> Self {
+        f16::EPSILON
+    }
+
     fn to_f32(self) -> f32 {
         self as f32
     }
@@ -44,6 +68,38 @@ impl Floating for f16 {
     fn to_f64(self) -> f64 {
         self as f64
     }
+
+    fn abs(self) -> Self {
+        f16::abs(self)
+    }
+
+    fn signum(self) -> Self {
+        f16::signum(self)
+    }
+
+    fn is_finite(self) -> bool {
+        f16::is_finite(self)
+    }
+
+    fn sqrt(self) -> Self {
+        f16::sqrt(self)
+    }
+
+    fn powf(self, exponent: Self) -> Self {
+        f16::powf(self, exponent)
+    }
+
+    fn ln(self) -> Self {
+        f16::ln(self)
+    }
+
+    fn min(self, other: Self) -> Self {
+        f16::min(self, other)
+    }
+
+    fn max(self, other: Self) -> Self {
+        f16::max(self, other)
+    }
 }
 
 impl Floating for f32 {
@@ -51,6 +107,10 @@ impl Floating for f32 {
         value as f32
     }
 
+    fn epsilon() -> Self {
+        f32::EPSILON
+    }
+
     fn to_f32(self) -> f32 {
         self
     }
@@ -58,6 +118,38 @@ impl Floating for f32 {
     fn to_f64(self) -> f64 {
         self as f64
     }
+
+    fn abs(self) -> Self {
+        f32::abs(self)
+    }
+
+    fn signum(self) -> Self {
+        f32::signum(self)
+    }
+
+    fn is_finite(self) -> bool {
+        f32::is_finite(self)
+    }
+
+    fn sqrt(self) -> Self {
+        f32::sqrt(self)
+    }
+
+    fn powf(self, exponent: Self) -> Self {
+        f32::powf(self, exponent)
+    }
+
+    fn ln(self) -> Self {
+        f32::ln(self)
+    }
+
+    fn min(self, other: Self) -> Self {
+        f32::min(self, other)
+    }
+
+    fn max(self, other: Self) -> Self {
+        f32::max(self, other)
+    }
 }
 
 impl Floating for f64 {
@@ -65,6 +157,10 @@ impl Floating for f64 {
         value
     }
 
+    fn epsilon() -> Self {
+        f64::EPSILON
+    }
+
     fn to_f32(self) -> f32 {
         self as f32
     }
@@ -72,6 +168,38 @@ impl Floating for f64 {
     fn to_f64(self) -> f64 {
         self
     }
+
+    fn abs(self) -> Self {
+        f64::abs(self)
+    }
+
+    fn signum(self) -> Self {
+        f64::signum(self)
+    }
+
+    fn is_finite(self) -> bool {
+        f64::is_finite(self)
+    }
+
+    fn sqrt(self) -> Self {
+        f64::sqrt(self)
+    }
+
+    fn powf(self, exponent: Self) -> Self {
+        f64::powf(self, exponent)
+    }
+
+    fn ln(self) -> Self {
+        f64::ln(self)
+    }
+
+    fn min(self, other: Self) -> Self {
+        f64::min(self, other)
+    }
+
+    fn max(self, other: Self) -> Self {
+        f64::max(self, other)
+    }
 }
 
 #[cfg(FALSE)]
@@ -80,6 +208,10 @@ impl Floating for f128 {
         value as f128
     }
 
+    fn epsilon() -> Self {
+        f128::EPSILON
+    }
+
     fn to_f32(self) -> f32 {
         self as f32
     }
@@ -87,4 +219,36 @@ impl Floating for f128 {
     fn to_f64(self) -> f64 {
         self as f64
     }
+
+    fn abs(self) -> Self {
+        f128::abs(self)
+    }
+
+    fn signum(self) -> Self {
+        f128::signum(self)
+    }
+
+    fn is_finite(self) -> bool {
+        f128::is_finite(self)
+    }
+
+    fn sqrt(self) -> Self {
+        f128::sqrt(self)
+    }
+
+    fn powf(self, exponent: Self) -> Self {
+        f128::powf(self, exponent)
+    }
+
+    fn ln(self) -> Self {
+        f128::ln(self)
+    }
+
+    fn min(self, other: Self) -> Self {
+        f128::min(self, other)
+    }
+
+    fn max(self, other: Self) -> Self {
+        f128::max(self, other)
+    }
 }
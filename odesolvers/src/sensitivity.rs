@@ -0,0 +1,222 @@
+use crate::error::SolverError;
+use crate::integration_shared::RuntimeStepControl;
+use crate::runtime_sized::RuntimeIntegrator;
+use crate::scalar::Floating;
+use crate::system::Autonomous;
+use crate::system::ParametricSystem;
+use crate::system::RuntimeSystem;
+
+// rows are state components, columns the initial state or parameter they are differentiated by
+#[derive(Clone, Copy, Debug)]
+pub struct Sensitivity<Float, const N: usize, const P: usize> {
+    pub time: Float,
+    pub state: [Float; N],
+    pub state_sensitivity: [[Float; N]; N],
+    pub parameter_sensitivity: [[Float; P]; N],
+}
+
+// the state, dy/dy0 and dy/dp integrated together as one runtime sized system
+struct Augmented<Float, const N: usize, const P: usize, System> {
+    system: System,
+    parameters: [Float; P],
+}
+
+impl<Float, const N: usize, const P: usize, System> RuntimeSystem<Float> for Augmented<Float, N, P, System>
+where
+    Float: Floating,
+    System: ParametricSystem<Float, N, P>,
+{
+    fn dynamics(&mut self, time: Float, augmented: &[Float]) -> Vec<Float> {
+        let (state, sensitivities) = split::<Float, N, P>(augmented);
+        let derivative = self.system.dynamics(time, &state, &self.parameters);
+        let jacobian = self.system.state_jacobian(time, &state, &self.parameters);
+        let forcing = self.system.parameter_jacobian(time, &state, &self.parameters);
+
+        // S' = J S for the initial state and Q' = J Q + df/dp for the parameters
+        let mut output = derivative.to_vec();
+        (0..N).for_each(|row| {
+            (0..N + P).for_each(|col| {
+                let product = (0..N)
+                    .fold(Float::default(), |sum, idx| sum + jacobian[row][idx] * sensitivities[idx][col]);
+                output.push(if col < N { product } else { product + forcing[row][col - N] });
+            })
+        });
+
+        output
+    }
+}
+
+pub struct SensitivityIntegrator<Float, const N: usize, const P: usize, System> {
+    integrator: RuntimeIntegrator<Float, Augmented<Float, N, P, System>>,
+    parameters: [Float; P],
+}
+
+impl<Float, const N: usize, const P: usize, Dynamics> SensitivityIntegrator<Float, N, P, Autonomous<Dynamics>>
+where
+    Float: Floating,
+    Dynamics: FnMut(&[Float; N], &[Float; P]) -> [Float; N],
+{
+    pub fn build(state: [Float; N], parameters: [Float; P], delta_time: Float, dynamics: Dynamics) -> Self {
+        Self::build_system(state, parameters, delta_time, Autonomous(dynamics))
+    }
+}
+
+impl<Float, const N: usize, const P: usize, Dynamics> SensitivityIntegrator<Float, N, P, Dynamics>
+where
+    Float: Floating,
+    Dynamics: FnMut(Float, &[Float; N], &[Float; P]) -> [Float; N],
+{
+    pub fn build_driven(
+        state: [Float; N],
+        parameters: [Float; P],
+        delta_time: Float,
+        dynamics: Dynamics,
+    ) -> Self {
+        Self::build_system(state, parameters, delta_time, dynamics)
+    }
+}
+
+impl<Float, const N: usize, const P: usize, System> SensitivityIntegrator<Float, N, P, System>
+where
+    Float: Floating,
+    System: ParametricSystem<Float, N, P>,
+{
+    // implement state_jacobian and parameter_jacobian on the system to replace the finite differences
+    pub fn build_system(
+        state: [Float; N],
+        parameters: [Float; P],
+        delta_time: Float,
+        system: System,
+    ) -> Self {
+        let augmented = Augmented { system, parameters };
+        SensitivityIntegrator {
+            integrator: RuntimeIntegrator::build_system(
+                initial::<Float, N, P>(&state),
+                delta_time,
+                augmented,
+            ),
+            parameters,
+        }
+    }
+
+    pub fn state(&self) -> [Float; N] {
+        split::<Float, N, P>(self.integrator.state()).0
+    }
+
+    pub const fn parameters(&self) -> [Float; P] {
+        self.parameters
+    }
+
+    pub fn sensitivity(&self) -> Sensitivity<Float, N, P> {
+        self.unpack(self.integrator.curr_time(), self.integrator.state())
+    }
+
+    pub fn delta_time(&self) -> Float {
+        self.integrator.delta_time()
+    }
+
+    pub fn curr_time(&self) -> Float {
+        self.integrator.curr_time()
+    }
+
+    // sensitivities restart from the identity whenever the initial point or the parameters change
+    pub fn set_time(&mut self, time: Float) -> &mut Self {
+        let state = self.state();
+        self.integrator.set_time(time).set_state(initial::<Float, N, P>(&state));
+        self
+    }
+
+    pub fn set_state(&mut self, state: [Float; N]) -> &mut Self {
+        self.integrator.set_state(initial::<Float, N, P>(&state));
+        self
+    }
+
+    pub fn set_parameters(&mut self, parameters: [Float; P]) -> &mut Self {
+        let state = self.state();
+        self.parameters = parameters;
+        self.integrator.system().parameters = parameters;
+        self.integrator.set_state(initial::<Float, N, P>(&state));
+        self
+    }
+
+    pub fn system(&mut self) -> &mut System {
+        &mut self.integrator.system().system
+    }
+
+    // tolerances cover the sensitivities as well as the state, per component they follow the state with one row of
+    // N + P sensitivities per state component after it
+    pub fn set_control(&mut self) -> &mut RuntimeStepControl<Float> {
+        self.integrator.set_control()
+    }
+
+    pub fn step(&mut self) -> Result<Sensitivity<Float, N, P>, SolverError<Float>> {
+        self.integrator.step()?;
+        Ok(self.sensitivity())
+    }
+
+    pub fn dynamic_step(&mut self) -> Result<Sensitivity<Float, N, P>, SolverError<Float>> {
+        self.integrator.dynamic_step()?;
+        Ok(self.sensitivity())
+    }
+
+    pub fn solve_with_time(
+        &mut self,
+        final_time: Float,
+    ) -> Result<Vec<Sensitivity<Float, N, P>>, SolverError<Float>> {
+        let output = self.integrator.solve_with_time(final_time)?;
+        Ok(output.into_iter().map(|(time, augmented)| self.unpack(time, &augmented)).collect())
+    }
+
+    pub fn solve_dynamic_with_time(
+        &mut self,
+        final_time: Float,
+    ) -> Result<Vec<Sensitivity<Float, N, P>>, SolverError<Float>> {
+        let output = self.integrator.solve_dynamic_with_time(final_time)?;
+        Ok(output.into_iter().map(|(time, augmented)| self.unpack(time, &augmented)).collect())
+    }
+
+    // adaptive steps that land exactly on every requested time, see RuntimeIntegrator::solve_at
+    pub fn solve_at(&mut self, times: &[Float]) -> Result<Vec<Sensitivity<Float, N, P>>, SolverError<Float>> {
+        let output = self.integrator.solve_at(times)?;
+        Ok(output.into_iter().map(|(time, augmented)| self.unpack(time, &augmented)).collect())
+    }
+
+    fn unpack(&self, time: Float, augmented: &[Float]) -> Sensitivity<Float, N, P> {
+        let (state, sensitivities) = split::<Float, N, P>(augmented);
+        let mut sensitivity = Sensitivity {
+            time,
+            state,
+            state_sensitivity: [[Float::default(); N]; N],
+            parameter_sensitivity: [[Float::default(); P]; N],
+        };
+        (0..N).for_each(|row| {
+            (0..N).for_each(|col| sensitivity.state_sensitivity[row][col] = sensitivities[row][col]);
+            (0..P).for_each(|col| sensitivity.parameter_sensitivity[row][col] = sensitivities[row][N + col]);
+        });
+
+        sensitivity
+    }
+}
+
+// the state, then one row of N + P sensitivities per state component
+fn initial<Float, const N: usize, const P: usize>(state: &[Float; N]) -> Vec<Float>
+where
+    Float: Floating,
+{
+    let mut augmented = state.to_vec();
+    (0..N).for_each(|row| {
+        (0..N + P).for_each(|col| augmented.push(Float::floatify(if row == col { 1. } else { 0. })))
+    });
+
+    augmented
+}
+
+fn split<Float, const N: usize, const P: usize>(augmented: &[Float]) -> ([Float; N], Vec<&[Float]>)
+where
+    Float: Floating,
+{
+    let mut state = [Float::default(); N];
+    state.copy_from_slice(&augmented[..N]);
+
+    (state, augmented[N..].chunks(N + P).collect())
+}
@@ -0,0 +1,234 @@
+use crate::error::SolverError;
+use crate::linear_algebra::Matrix;
+use crate::runge_kutta::Integrator;
+use crate::scalar::Floating;
+use crate::system::Autonomous;
+use crate::system::OdeSystem;
+
+// two point boundary value problems, the unknown initial states are found by newton iteration
+pub struct Shooting<Float, const N: usize, System> {
+    integrator: Integrator<Float, N, System>,
+    start: Float,
+    end: Float,
+    tolerance: Float,
+    max_iterations: usize,
+    iterations: usize,
+    residual: Float,
+}
+
+impl<Float, const N: usize, Dynamics> Shooting<Float, N, Autonomous<Dynamics>>
+where
+    Float: Floating,
+    Dynamics: FnMut(&[Float; N]) -> [Float; N],
+{
+    pub fn build(start: Float, end: Float, delta_time: Float, dynamics: Dynamics) -> Self {
+        Self::build_system(start, end, delta_time, Autonomous(dynamics))
+    }
+}
+
+impl<Float, const N: usize, Dynamics> Shooting<Float, N, Dynamics>
+where
+    Float: Floating,
+    Dynamics: FnMut(Float, &[Float; N]) -> [Float; N],
+{
+    pub fn build_driven(start: Float, end: Float, delta_time: Float, dynamics: Dynamics) -> Self {
+        Self::build_system(start, end, delta_time, dynamics)
+    }
+}
+
+impl<Float, const N: usize, System> Shooting<Float, N, System>
+where
+    Float: Floating,
+    System: OdeSystem<Float, N>,
+{
+    const TOLERANCE_DEFAULT: f64 = 1e-10;
+    const MAX_ITERATIONS_DEFAULT: usize = 50;
+    // newton steps are halved until the residual drops, this far and no further
+    const MIN_DAMPING: f64 = 1. / 1024.;
+
+    // fixed rk4 steps of delta_time, so the finite difference jacobian sees a smooth map
+    pub fn build_system(start: Float, end: Float, delta_time: Float, system: System) -> Self {
+        Shooting {
+            integrator: Integrator::build_system([Float::default(); N], delta_time, system),
+            start,
+            end,
+            tolerance: Float::floatify(Self::TOLERANCE_DEFAULT),
+            max_iterations: Self::MAX_ITERATIONS_DEFAULT,
+            iterations: 0,
+            residual: Float::default(),
+        }
+    }
+
+    pub fn integrator(&mut self) -> &mut Integrator<Float, N, System> {
+        &mut self.integrator
+    }
+
+    // root mean square of the boundary and continuity residuals
+    pub fn set_tolerance(&mut self, tolerance: Float) -> &mut Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn set_max_iterations(&mut self, iterations: usize) -> &mut Self {
+        self.max_iterations = iterations;
+        self
+    }
+
+    pub const fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    pub const fn residual(&self) -> Float {
+        self.residual
+    }
+
+    // boundary returns one residual per state component from the states at both ends, zero when satisfied
+    pub fn solve(
+        &mut self,
+        guess: [Float; N],
+        boundary: impl FnMut(&[Float; N], &[Float; N]) -> [Float; N],
+    ) -> Result<Vec<(Float, [Float; N])>, SolverError<Float>> {
+        self.solve_multiple(1, |_| guess, boundary)
+    }
+
+    // the interval is split evenly, every node gets its own unknown state so errors cannot grow across the
+    // whole interval, guess gives the starting state at each node
+    pub fn solve_multiple(
+        &mut self,
+        intervals: usize,
+        mut guess: impl FnMut(Float) -> [Float; N],
+        mut boundary: impl FnMut(&[Float; N], &[Float; N]) -> [Float; N],
+    ) -> Result<Vec<(Float, [Float; N])>, SolverError<Float>> {
+        let intervals = intervals.max(1);
+        let span = self.end - self.start;
+        let nodes: Vec<Float> = (0..=intervals)
+            .map(|idx| match idx == intervals {
+                true => self.end,
+                false => self.start + span * Float::floatify(idx as f64 / intervals as f64),
+            })
+            .collect();
+        let unknowns: Vec<Float> = nodes[..intervals].iter().flat_map(|&node| guess(node)).collect();
+
+        let unknowns = self.newton(&nodes, unknowns, &mut boundary)?;
+        self.trajectory(&nodes, &unknowns)
+    }
+
+    fn newton(
+        &mut self,
+        nodes: &[Float],
+        mut unknowns: Vec<Float>,
+        boundary: &mut impl FnMut(&[Float; N], &[Float; N]) -> [Float; N],
+    ) -> Result<Vec<Float>, SolverError<Float>> {
+        let mut residuals = self.residuals(nodes, &unknowns, boundary)?;
+        self.residual = rms(&residuals);
+        self.iterations = 0;
+
+        while self.residual > self.tolerance {
+            if self.iterations >= self.max_iterations {
+                return Err(SolverError::NoConvergence {
+                    iterations: self.iterations,
+                    residual: self.residual,
+                });
+            }
+            self.iterations += 1;
+
+            let size = unknowns.len();
+            let mut jacobian = Matrix::build(size, size);
+            for col in 0..size {
+                let delta = Float::epsilon().sqrt() * unknowns[col].abs().max(Float::floatify(1.));
+                let mut perturbed = unknowns.clone();
+                perturbed[col] += delta;
+                let shifted = self.residuals(nodes, &perturbed, boundary)?;
+                (0..size).for_each(|row| jacobian[(row, col)] = (shifted[row] - residuals[row]) / delta);
+            }
+            let Some(decomposition) = jacobian.lu() else {
+                return Err(SolverError::NoConvergence {
+                    iterations: self.iterations,
+                    residual: self.residual,
+                });
+            };
+            let step = decomposition.solve(&residuals);
+
+            // damped, a full step that blows up or does not reduce the residual enough is halved
+            let mut damping = Float::floatify(1.);
+            loop {
+                let trial: Vec<Float> =
+                    unknowns.iter().zip(&step).map(|(&unknown, &step)| unknown - step * damping).collect();
+                let sufficient = Float::floatify(1.) - damping * Float::floatify(0.5);
+                if let Ok(trial_residuals) = self.residuals(nodes, &trial, boundary)
+                    && rms(&trial_residuals) < sufficient * self.residual
+                {
+                    (unknowns, residuals) = (trial, trial_residuals);
+                    self.residual = rms(&residuals);
+                    break;
+                }
+
+                damping *= Float::floatify(0.5);
+                if damping < Float::floatify(Self::MIN_DAMPING) {
+                    return Err(SolverError::NoConvergence {
+                        iterations: self.iterations,
+                        residual: self.residual,
+                    });
+                }
+            }
+        }
+
+        Ok(unknowns)
+    }
+
+    // boundary conditions first, then the mismatch between each segment end and the next node
+    fn residuals(
+        &mut self,
+        nodes: &[Float],
+        unknowns: &[Float],
+        boundary: &mut impl FnMut(&[Float; N], &[Float; N]) -> [Float; N],
+    ) -> Result<Vec<Float>, SolverError<Float>> {
+        let intervals = nodes.len() - 1;
+        let mut ends = Vec::with_capacity(intervals);
+        for segment in 0..intervals {
+            let start = node_state(unknowns, segment);
+            self.integrator.set_time(nodes[segment]).set_state(start);
+            self.integrator.solve_until(nodes[segment + 1])?;
+            ends.push(self.integrator.state());
+        }
+
+        let mut residuals = boundary(&node_state(unknowns, 0), &ends[intervals - 1]).to_vec();
+        (1..intervals).for_each(|segment| {
+            let start: [Float; N] = node_state(unknowns, segment);
+            residuals.extend((0..N).map(|idx| ends[segment - 1][idx] - start[idx]));
+        });
+
+        Ok(residuals)
+    }
+
+    fn trajectory(
+        &mut self,
+        nodes: &[Float],
+        unknowns: &[Float],
+    ) -> Result<Vec<(Float, [Float; N])>, SolverError<Float>> {
+        let mut trajectory = vec![(nodes[0], node_state(unknowns, 0))];
+        for segment in 0..nodes.len() - 1 {
+            self.integrator.set_time(nodes[segment]).set_state(node_state(unknowns, segment));
+            trajectory.extend(self.integrator.solve_with_time(nodes[segment + 1])?);
+        }
+
+        Ok(trajectory)
+    }
+}
+
+fn node_state<Float, const N: usize>(unknowns: &[Float], node: usize) -> [Float; N]
+where
+    Float: Floating,
+{
+    let mut state = [Float::default(); N];
+    state.copy_from_slice(&unknowns[node * N..(node + 1) * N]);
+    state
+}
+
+fn rms<Float>(values: &[Float]) -> Float
+where
+    Float: Floating,
+{
+    let squares = values.iter().fold(Float::default(), |sum, &value| sum + value * value);
+    (squares / Float::floatify(values.len().max(1) as f64)).sqrt()
+}
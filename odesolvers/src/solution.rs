@@ -0,0 +1,101 @@
+use crate::integration_shared::HermiteSegment;
+use crate::integration_shared::RuntimeState;
+use crate::integration_shared::State;
+use crate::integration_shared::StepState;
+use crate::scalar::Floating;
+
+pub type Solution<Float, const N: usize> = StateSolution<Float, State<Float, N>>;
+
+pub type RuntimeSolution<Float> = StateSolution<Float, RuntimeState<Float>>;
+
+#[derive(Clone)]
+pub struct StateSolution<Float, Vector> {
+    times: Vec<Float>,
+    states: Vec<Vector>,
+    derivatives: Vec<Vector>,
+}
+
+impl<Float, Vector> StateSolution<Float, Vector>
+where
+    Float: Floating,
+    Vector: StepState<Float>,
+{
+    pub fn build(time: Float, state: Vector::Values, derivative: Vector::Values) -> Self {
+        StateSolution {
+            times: vec![time],
+            states: vec![Vector::from_values(state)],
+            derivatives: vec![Vector::from_values(derivative)],
+        }
+    }
+
+    pub fn push(&mut self, time: Float, state: Vector::Values, derivative: Vector::Values) {
+        self.times.push(time);
+        self.states.push(Vector::from_values(state));
+        self.derivatives.push(Vector::from_values(derivative));
+    }
+
+    // moves the last time without touching its state, for steps snapped onto a requested time
+    pub(crate) fn retime_last(&mut self, time: Float) {
+        if let Some(last) = self.times.last_mut() {
+            *last = time;
+        }
+    }
+
+    pub fn extend(&mut self, points: impl IntoIterator<Item = (Float, Vector::Values, Vector::Values)>) {
+        points.into_iter().for_each(|(time, state, derivative)| self.push(time, state, derivative));
+    }
+
+    pub fn times(&self) -> &[Float] {
+        &self.times
+    }
+
+    pub fn states(&self) -> Vec<Vector::Values> {
+        self.states.iter().map(Vector::values).collect()
+    }
+
+    pub fn start_time(&self) -> Float {
+        self.times[0]
+    }
+
+    pub fn final_time(&self) -> Float {
+        self.times[self.times.len() - 1]
+    }
+
+    pub fn contains(&self, time: Float) -> bool {
+        let (start, end) = (self.start_time(), self.final_time());
+        time >= start.min(end) && time <= start.max(end)
+    }
+
+    pub fn evaluate(&self, time: Float) -> Option<Vector::Values> {
+        if !self.contains(time) {
+            return None;
+        }
+        if self.times.len() == 1 {
+            return Some(self.states[0].values());
+        }
+
+        // times run backwards when the solution was integrated backwards
+        let forward = self.final_time() >= self.start_time();
+        let index = self.times.partition_point(|&step| if forward { step <= time } else { step >= time });
+        let index = index.clamp(1, self.times.len() - 1) - 1;
+        let segment = HermiteSegment::build(
+            (self.times[index], self.states[index].clone(), self.derivatives[index].clone()),
+            (self.times[index + 1], self.states[index + 1].clone(), self.derivatives[index + 1].clone()),
+        );
+
+        Some(segment.evaluate(time))
+    }
+
+    // evenly spaced samples across the whole span, including both ends
+    pub fn sample(&self, count: usize) -> Vec<(Float, Vector::Values)> {
+        let span = self.final_time() - self.start_time();
+        let intervals = Float::floatify(count.saturating_sub(1).max(1) as f64);
+        (0..count)
+            .filter_map(|idx| {
+                let time = self.start_time() + span * Float::floatify(idx as f64) / intervals;
+                let time = if idx + 1 == count { self.final_time() } else { time };
+                self.evaluate(time).map(|state| (time, state))
+            })
+            .collect()
+    }
+}
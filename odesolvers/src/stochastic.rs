@@ -0,0 +1,256 @@
+use std::ops::ControlFlow;
+
+use crate::error::SolverError;
+use crate::integration_shared::solve_clamped;
+use crate::integration_shared::ClampedSolve;
+use crate::integration_shared::State;
+use crate::integration_shared::StepControl;
+use crate::integration_shared::StepState;
+use crate::random::Random;
+use crate::scalar::Floating;
+use crate::system::Autonomous;
+use crate::system::OdeSystem;
+
+// times and states of one realisation, shaped like the output of solve_with_time
+pub type SamplePath<Float, const N: usize> = Vec<(Float, [Float; N])>;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum StochasticMethod {
+    // strong order one half
+    EulerMaruyama,
+    // strong order one, differentiates the diffusion through its jacobian
+    Milstein,
+    // strong order one like milstein, with the derivative replaced by an extra diffusion evaluation
+    StochasticRungeKutta,
+}
+
+// ito equations dX = f dt + g dW with diagonal noise, every component has its own wiener process
+pub struct StochasticIntegrator<Float, const N: usize, Drift, Diffusion> {
+    state: State<Float, N>,
+    dt: Float,
+    drift: Drift,
+    diffusion: Diffusion,
+    time: Float,
+    wiener: State<Float, N>,
+    random: Random,
+    method: StochasticMethod,
+    control: StepControl<Float, N>,
+}
+
+impl<Float, const N: usize, Drift, Diffusion>
+    StochasticIntegrator<Float, N, Autonomous<Drift>, Autonomous<Diffusion>>
+where
+    Float: Floating,
+    Drift: FnMut(&[Float; N]) -> [Float; N],
+    Diffusion: FnMut(&[Float; N]) -> [Float; N],
+{
+    pub fn build(
+        state: [Float; N],
+        delta_time: Float,
+        seed: u64,
+        drift: Drift,
+        diffusion: Diffusion,
+    ) -> Self {
+        Self::build_system(state, delta_time, seed, Autonomous(drift), Autonomous(diffusion))
+    }
+}
+
+impl<Float, const N: usize, Drift, Diffusion> StochasticIntegrator<Float, N, Drift, Diffusion>
+where
+    Float: Floating,
+    Drift: FnMut(Float, &[Float; N]) -> [Float; N],
+    Diffusion: FnMut(Float, &[Float; N]) -> [Float; N],
+{
+    pub fn build_driven(
+        state: [Float; N],
+        delta_time: Float,
+        seed: u64,
+        drift: Drift,
+        diffusion: Diffusion,
+    ) -> Self {
+        Self::build_system(state, delta_time, seed, drift, diffusion)
+    }
+}
+
+impl<Float, const N: usize, Drift, Diffusion> StochasticIntegrator<Float, N, Drift, Diffusion>
+where
+    Float: Floating,
+    Drift: OdeSystem<Float, N>,
+    Diffusion: OdeSystem<Float, N>,
+{
+    pub fn build_system(
+        state: [Float; N],
+        delta_time: Float,
+        seed: u64,
+        drift: Drift,
+        diffusion: Diffusion,
+    ) -> Self {
+        StochasticIntegrator {
+            state: State::build(state),
+            dt: delta_time,
+            drift,
+            diffusion,
+            time: Float::default(),
+            wiener: State::build([Float::default(); N]),
+            random: Random::build(seed),
+            method: StochasticMethod::EulerMaruyama,
+            control: StepControl::build(),
+        }
+    }
+
+    pub const fn state(&self) -> [Float; N] {
+        self.state.values()
+    }
+
+    pub const fn delta_time(&self) -> Float {
+        self.dt
+    }
+
+    pub const fn curr_time(&self) -> Float {
+        self.time
+    }
+
+    // the driving wiener processes, summed since the last reset, for comparing against exact solutions
+    pub const fn wiener(&self) -> [Float; N] {
+        self.wiener.values()
+    }
+
+    pub fn set_time(&mut self, time: Float) -> &mut Self {
+        self.time = time;
+        self
+    }
+
+    pub fn set_state(&mut self, state: [Float; N]) -> &mut Self {
+        self.state = State::build(state);
+        self.wiener = State::build([Float::default(); N]);
+        self
+    }
+
+    // restarts the noise, the same seed replays the same sample path
+    pub fn set_seed(&mut self, seed: u64) -> &mut Self {
+        self.random = Random::build(seed);
+        self
+    }
+
+    pub fn set_method(&mut self, method: StochasticMethod) -> &mut Self {
+        self.method = method;
+        self
+    }
+
+    // only max_steps applies, the step size is never adapted
+    pub fn set_control(&mut self) -> &mut StepControl<Float, N> {
+        &mut self.control
+    }
+
+    pub fn step(&mut self) -> [Float; N] {
+        let scale = self.dt.abs().sqrt();
+        let mut increment = [Float::default(); N];
+        increment.iter_mut().for_each(|value| *value = Float::floatify(self.random.normal()) * scale);
+        self.step_with(increment)
+    }
+
+    // takes the step with given wiener increments, each drawn from a normal with variance |dt|
+    pub fn step_with(&mut self, increment: [Float; N]) -> [Float; N] {
+        let (dt, time, state) = (self.dt, self.time, self.state);
+        let drift = State::build(self.drift.dynamics(time, &state.inner));
+        let diffusion = self.diffusion.dynamics(time, &state.inner);
+
+        let mut next = state + drift * dt;
+        (0..N).for_each(|idx| next.inner[idx] += diffusion[idx] * increment[idx]);
+
+        // ito correction, half of g g' (dW^2 - dt) for every component
+        let half = Float::floatify(0.5);
+        match self.method {
+            StochasticMethod::EulerMaruyama => {}
+            StochasticMethod::Milstein => {
+                let jacobian = self.diffusion.jacobian(time, &state.inner);
+                (0..N).for_each(|idx| {
+                    let correction = increment[idx] * increment[idx] - dt.abs();
+                    next.inner[idx] += half * diffusion[idx] * jacobian[idx][idx] * correction;
+                });
+            }
+            StochasticMethod::StochasticRungeKutta => {
+                // platen's support value, g(support) - g(x) approximates g g' sqrt(dt)
+                let root = dt.abs().sqrt();
+                let mut support = state + drift * dt;
+                (0..N).for_each(|idx| support.inner[idx] += diffusion[idx] * root);
+                let shifted = self.diffusion.dynamics(time, &support.inner);
+                (0..N).for_each(|idx| {
+                    let correction = increment[idx] * increment[idx] - dt.abs();
+                    next.inner[idx] += half * (shifted[idx] - diffusion[idx]) * correction / root;
+                });
+            }
+        }
+
+        self.state = next;
+        self.wiener = self.wiener + State::build(increment);
+        self.time += dt;
+
+        self.state()
+    }
+
+    pub fn solve_until(&mut self, final_time: Float) -> Result<Vec<[Float; N]>, SolverError<Float>> {
+        Ok(self.solve_with_time(final_time)?.into_iter().map(|(_, state)| state).collect())
+    }
+
+    pub fn solve_with_time(
+        &mut self,
+        final_time: Float,
+    ) -> Result<Vec<(Float, [Float; N])>, SolverError<Float>> {
+        // the wiener increments are drawn forward in time, a reversed path would need a brownian bridge
+        if final_time < self.time {
+            return Err(SolverError::BackwardIntegration { time: self.time, final_time });
+        }
+        if !self.dt.is_finite() || self.dt == Float::default() {
+            return Err(SolverError::InvalidStepSize { step: self.dt });
+        }
+
+        solve_clamped(
+            self,
+            final_time,
+            |integrator| Ok(integrator.step()),
+            |integrator, state| ControlFlow::Continue((integrator.time, state)),
+        )
+    }
+
+    // independent paths from the current time and state, the noise keeps running from path to path
+    pub fn sample_paths(
+        &mut self,
+        final_time: Float,
+        count: usize,
+    ) -> Result<Vec<SamplePath<Float, N>>, SolverError<Float>> {
+        let (time, state) = (self.time, self.state());
+        (0..count)
+            .map(|_| {
+                self.set_time(time).set_state(state);
+                self.solve_with_time(final_time)
+            })
+            .collect()
+    }
+}
+
+impl<Float, const N: usize, Drift, Diffusion> ClampedSolve<Float>
+    for StochasticIntegrator<Float, N, Drift, Diffusion>
+where
+    Float: Floating,
+{
+    fn time(&mut self) -> &mut Float {
+        &mut self.time
+    }
+
+    fn step_size(&mut self) -> &mut Float {
+        &mut self.dt
+    }
+
+    fn max_steps(&self) -> usize {
+        self.control.max_steps
+    }
+
+    fn min_step(&mut self) -> &mut Float {
+        &mut self.control.min_step
+    }
+
+    fn is_finite(&self) -> bool {
+        self.state.is_finite()
+    }
+}
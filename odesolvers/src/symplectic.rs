@@ -0,0 +1,204 @@
+use std::ops::ControlFlow;
+
+use crate::error::SolverError;
+use crate::integration_shared::solve_clamped;
+use crate::integration_shared::ClampedSolve;
+use crate::integration_shared::State;
+use crate::integration_shared::StepControl;
+use crate::integration_shared::StepState;
+use crate::scalar::Floating;
+
+// position and momentum, or position and velocity for second order systems
+pub type PhasePoint<Float, const N: usize> = ([Float; N], [Float; N]);
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SymplecticMethod {
+    // kick, drift, kick
+    StormerVerlet,
+    // drift, kick, drift
+    Leapfrog,
+    ForestRuth,
+    Yoshida6,
+}
+
+// separable hamiltonians only, the velocity may depend on the momentum alone and the force on the position alone
+pub struct SymplecticIntegrator<Float, const N: usize, Velocity, Force> {
+    position: State<Float, N>,
+    momentum: State<Float, N>,
+    dt: Float,
+    time: Float,
+    velocity: Velocity,
+    force: Force,
+    method: SymplecticMethod,
+    // the force at the current position, the last kick of a step evaluates it for the first kick of the next
+    last_force: Option<State<Float, N>>,
+    control: StepControl<Float, N>,
+}
+
+impl<Float, const N: usize, Velocity, Force> SymplecticIntegrator<Float, N, Velocity, Force>
+where
+    Float: Floating,
+    Velocity: FnMut(&[Float; N]) -> [Float; N],
+    Force: FnMut(&[Float; N]) -> [Float; N],
+{
+    pub fn build(
+        position: [Float; N],
+        momentum: [Float; N],
+        delta_time: Float,
+        velocity: Velocity,
+        force: Force,
+    ) -> Self {
+        SymplecticIntegrator {
+            position: State::build(position),
+            momentum: State::build(momentum),
+            dt: delta_time,
+            time: Float::default(),
+            velocity,
+            force,
+            method: SymplecticMethod::StormerVerlet,
+            last_force: None,
+            control: StepControl::build(),
+        }
+    }
+
+    pub const fn position(&self) -> [Float; N] {
+        self.position.values()
+    }
+
+    pub const fn momentum(&self) -> [Float; N] {
+        self.momentum.values()
+    }
+
+    pub const fn state(&self) -> PhasePoint<Float, N> {
+        (self.position.values(), self.momentum.values())
+    }
+
+    pub const fn delta_time(&self) -> Float {
+        self.dt
+    }
+
+    pub const fn curr_time(&self) -> Float {
+        self.time
+    }
+
+    pub fn set_time(&mut self, time: Float) -> &mut Self {
+        self.time = time;
+        self
+    }
+
+    pub fn set_state(&mut self, position: [Float; N], momentum: [Float; N]) -> &mut Self {
+        self.position = State::build(position);
+        self.momentum = State::build(momentum);
+        self.last_force = None;
+        self
+    }
+
+    pub fn set_method(&mut self, method: SymplecticMethod) -> &mut Self {
+        self.method = method;
+        self
+    }
+
+    // only max_steps applies, the step size is never adapted
+    pub fn set_control(&mut self) -> &mut StepControl<Float, N> {
+        &mut self.control
+    }
+
+    pub fn step(&mut self) -> PhasePoint<Float, N> {
+        match self.method {
+            SymplecticMethod::StormerVerlet => self.compose(&[1.]),
+            SymplecticMethod::Leapfrog => self.leapfrog(),
+            SymplecticMethod::ForestRuth => {
+                // triple jump, the middle step runs backwards to cancel the third order error
+                let outer = 1. / (2. - 2f64.powf(1. / 3.));
+                let inner = 1. - 2. * outer;
+                self.compose(&[outer, inner, outer]);
+            }
+            SymplecticMethod::Yoshida6 => {
+                let outer = [0.784513610477560, 0.235573213359357, -1.17767998417887];
+                let inner = 1. - 2. * outer.iter().sum::<f64>();
+                self.compose(&[outer[0], outer[1], outer[2], inner, outer[2], outer[1], outer[0]]);
+            }
+        }
+        self.time += self.dt;
+
+        self.state()
+    }
+
+    pub fn solve_until(
+        &mut self,
+        final_time: Float,
+    ) -> Result<Vec<PhasePoint<Float, N>>, SolverError<Float>> {
+        Ok(self.solve_with_time(final_time)?.into_iter().map(|(_, point)| point).collect())
+    }
+
+    pub fn solve_with_time(
+        &mut self,
+        final_time: Float,
+    ) -> Result<Vec<(Float, PhasePoint<Float, N>)>, SolverError<Float>> {
+        solve_clamped(
+            self,
+            final_time,
+            |integrator| Ok(integrator.step()),
+            |integrator, point| ControlFlow::Continue((integrator.time, point)),
+        )
+    }
+
+    fn kick(&mut self, fraction: Float) {
+        let force = match self.last_force {
+            Some(force) => force,
+            None => State::build((self.force)(&self.position.inner)),
+        };
+        self.last_force = Some(force);
+        self.momentum = self.momentum + force * (self.dt * fraction);
+    }
+
+    fn drift(&mut self, fraction: Float) {
+        let velocity = State::build((self.velocity)(&self.momentum.inner));
+        self.position = self.position + velocity * (self.dt * fraction);
+        self.last_force = None;
+    }
+
+    // kick, drift, kick verlet steps of the given weights run back to back, the closing half kick of one and the
+    // opening half kick of the next share a position and merge into a single kick
+    fn compose(&mut self, weights: &[f64]) {
+        self.kick(Float::floatify(weights[0] * 0.5));
+        weights.iter().enumerate().for_each(|(idx, &weight)| {
+            self.drift(Float::floatify(weight));
+            let next = weights.get(idx + 1).copied().unwrap_or_default();
+            self.kick(Float::floatify((weight + next) * 0.5));
+        });
+    }
+
+    fn leapfrog(&mut self) {
+        let half = Float::floatify(0.5);
+        self.drift(half);
+        self.kick(Float::floatify(1.));
+        self.drift(half);
+    }
+}
+
+impl<Float, const N: usize, Velocity, Force> ClampedSolve<Float>
+    for SymplecticIntegrator<Float, N, Velocity, Force>
+where
+    Float: Floating,
+{
+    fn time(&mut self) -> &mut Float {
+        &mut self.time
+    }
+
+    fn step_size(&mut self) -> &mut Float {
+        &mut self.dt
+    }
+
+    fn max_steps(&self) -> usize {
+        self.control.max_steps
+    }
+
+    fn min_step(&mut self) -> &mut Float {
+        &mut self.control.min_step
+    }
+
+    fn is_finite(&self) -> bool {
+        self.position.is_finite() && self.momentum.is_finite()
+    }
+}
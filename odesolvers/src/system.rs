@@ -0,0 +1,241 @@
+use crate::scalar::Floating;
+
+pub trait OdeSystem<Float, const N: usize> {
+    fn dynamics(&mut self, time: Float, state: &[Float; N]) -> [Float; N];
+
+    // rows are components of the dynamics, columns the state they are differentiated by
+    fn jacobian(&mut self, time: Float, state: &[Float; N]) -> [[Float; N]; N]
+    where
+        Float: Floating,
+    {
+        finite_difference_jacobian(self, time, state)
+    }
+
+    // explicit dependence of the dynamics on time, needed by the rosenbrock methods
+    fn time_derivative(&mut self, time: Float, state: &[Float; N]) -> [Float; N]
+    where
+        Float: Floating,
+    {
+        let delta = Float::epsilon().sqrt() * time.abs().max(Float::floatify(1.));
+        let (base, shifted) = (self.dynamics(time, state), self.dynamics(time + delta, state));
+        let mut derivative = [Float::default(); N];
+        (0..N).for_each(|idx| derivative[idx] = (shifted[idx] - base[idx]) / delta);
+        derivative
+    }
+}
+
+pub fn finite_difference_jacobian<Float, const N: usize>(
+    system: &mut (impl OdeSystem<Float, N> + ?Sized),
+    time: Float,
+    state: &[Float; N],
+) -> [[Float; N]; N]
+where
+    Float: Floating,
+{
+    let base = system.dynamics(time, state);
+    let mut jacobian = [[Float::default(); N]; N];
+    (0..N).for_each(|col| {
+        let delta = Float::epsilon().sqrt() * state[col].abs().max(Float::floatify(1.));
+        let mut perturbed = *state;
+        perturbed[col] += delta;
+        let shifted = system.dynamics(time, &perturbed);
+        (0..N).for_each(|row| jacobian[row][col] = (shifted[row] - base[row]) / delta);
+    });
+
+    jacobian
+}
+
+impl<Float, const N: usize, Dynamics> OdeSystem<Float, N> for Dynamics
+where
+    Dynamics: FnMut(Float, &[Float; N]) -> [Float; N],
+{
+    fn dynamics(&mut self, time: Float, state: &[Float; N]) -> [Float; N] {
+        self(time, state)
+    }
+}
+
+// wraps dynamics that do not depend on time so they can be used as a system directly
+#[derive(Clone, Copy)]
+pub struct Autonomous<Dynamics>(pub Dynamics);
+
+impl<Float, const N: usize, Dynamics> OdeSystem<Float, N> for Autonomous<Dynamics>
+where
+    Dynamics: FnMut(&[Float; N]) -> [Float; N],
+{
+    fn dynamics(&mut self, _time: Float, state: &[Float; N]) -> [Float; N] {
+        (self.0)(state)
+    }
+
+    fn time_derivative(&mut self, _time: Float, _state: &[Float; N]) -> [Float; N]
+    where
+        Float: Floating,
+    {
+        [Float::default(); N]
+    }
+}
+
+impl<Float, const N: usize> OdeSystem<Float, N> for Box<dyn OdeSystem<Float, N> + '_> {
+    fn dynamics(&mut self, time: Float, state: &[Float; N]) -> [Float; N] {
+        self.as_mut().dynamics(time, state)
+    }
+
+    fn jacobian(&mut self, time: Float, state: &[Float; N]) -> [[Float; N]; N]
+    where
+        Float: Floating,
+    {
+        self.as_mut().jacobian(time, state)
+    }
+
+    fn time_derivative(&mut self, time: Float, state: &[Float; N]) -> [Float; N]
+    where
+        Float: Floating,
+    {
+        self.as_mut().time_derivative(time, state)
+    }
+}
+
+// same as OdeSystem, but the dimension is only known once the system is running
+pub trait RuntimeSystem<Float> {
+    fn dynamics(&mut self, time: Float, state: &[Float]) -> Vec<Float>;
+}
+
+impl<Float, Dynamics> RuntimeSystem<Float> for Dynamics
+where
+    Dynamics: FnMut(Float, &[Float]) -> Vec<Float>,
+{
+    fn dynamics(&mut self, time: Float, state: &[Float]) -> Vec<Float> {
+        self(time, state)
+    }
+}
+
+impl<Float, Dynamics> RuntimeSystem<Float> for Autonomous<Dynamics>
+where
+    Dynamics: FnMut(&[Float]) -> Vec<Float>,
+{
+    fn dynamics(&mut self, _time: Float, state: &[Float]) -> Vec<Float> {
+        (self.0)(state)
+    }
+}
+
+// x'' = f(t, x, x'), with position and velocity kept apart
+pub trait SecondOrderSystem<Float, const N: usize> {
+    fn acceleration(&mut self, time: Float, position: &[Float; N], velocity: &[Float; N]) -> [Float; N];
+
+    // nystrom steppers skip velocity stages when the acceleration ignores the velocity
+    fn velocity_dependent(&self) -> bool {
+        true
+    }
+}
+
+impl<Float, const N: usize, Acceleration> SecondOrderSystem<Float, N> for Acceleration
+where
+    Acceleration: FnMut(Float, &[Float; N], &[Float; N]) -> [Float; N],
+{
+    fn acceleration(&mut self, time: Float, position: &[Float; N], velocity: &[Float; N]) -> [Float; N] {
+        self(time, position, velocity)
+    }
+}
+
+impl<Float, const N: usize, Acceleration> SecondOrderSystem<Float, N> for Autonomous<Acceleration>
+where
+    Acceleration: FnMut(&[Float; N], &[Float; N]) -> [Float; N],
+{
+    fn acceleration(&mut self, _time: Float, position: &[Float; N], velocity: &[Float; N]) -> [Float; N] {
+        (self.0)(position, velocity)
+    }
+}
+
+// wraps accelerations that only depend on the position, such as undamped mechanical systems
+#[derive(Clone, Copy)]
+pub struct Conservative<Acceleration>(pub Acceleration);
+
+impl<Float, const N: usize, Acceleration> SecondOrderSystem<Float, N> for Conservative<Acceleration>
+where
+    Acceleration: FnMut(&[Float; N]) -> [Float; N],
+{
+    fn acceleration(&mut self, _time: Float, position: &[Float; N], _velocity: &[Float; N]) -> [Float; N] {
+        (self.0)(position)
+    }
+
+    fn velocity_dependent(&self) -> bool {
+        false
+    }
+}
+
+// y'(t) = f(t, y(t), y(t - tau_1), ..., y(t - tau_k)), the delayed states arrive in the order of the delays
+pub trait DelaySystem<Float, const N: usize> {
+    fn dynamics(&mut self, time: Float, state: &[Float; N], delayed: &[[Float; N]]) -> [Float; N];
+}
+
+impl<Float, const N: usize, Dynamics> DelaySystem<Float, N> for Dynamics
+where
+    Dynamics: FnMut(Float, &[Float; N], &[[Float; N]]) -> [Float; N],
+{
+    fn dynamics(&mut self, time: Float, state: &[Float; N], delayed: &[[Float; N]]) -> [Float; N] {
+        self(time, state, delayed)
+    }
+}
+
+impl<Float, const N: usize, Dynamics> DelaySystem<Float, N> for Autonomous<Dynamics>
+where
+    Dynamics: FnMut(&[Float; N], &[[Float; N]]) -> [Float; N],
+{
+    fn dynamics(&mut self, _time: Float, state: &[Float; N], delayed: &[[Float; N]]) -> [Float; N] {
+        (self.0)(state, delayed)
+    }
+}
+
+// dynamics that depend on a parameter vector, for sensitivity analysis and fitting
+pub trait ParametricSystem<Float, const N: usize, const P: usize> {
+    fn dynamics(&mut self, time: Float, state: &[Float; N], parameters: &[Float; P]) -> [Float; N];
+
+    // rows are components of the dynamics, columns the state they are differentiated by
+    fn state_jacobian(&mut self, time: Float, state: &[Float; N], parameters: &[Float; P]) -> [[Float; N]; N]
+    where
+        Float: Floating,
+    {
+        let mut fixed = |time: Float, state: &[Float; N]| self.dynamics(time, state, parameters);
+        finite_difference_jacobian(&mut fixed, time, state)
+    }
+
+    // rows are components of the dynamics, columns the parameters they are differentiated by
+    fn parameter_jacobian(
+        &mut self,
+        time: Float,
+        state: &[Float; N],
+        parameters: &[Float; P],
+    ) -> [[Float; P]; N]
+    where
+        Float: Floating,
+    {
+        let base = self.dynamics(time, state, parameters);
+        let mut jacobian = [[Float::default(); P]; N];
+        (0..P).for_each(|col| {
+            let delta = Float::epsilon().sqrt() * parameters[col].abs().max(Float::floatify(1.));
+            let mut perturbed = *parameters;
+            perturbed[col] += delta;
+            let shifted = self.dynamics(time, state, &perturbed);
+            (0..N).for_each(|row| jacobian[row][col] = (shifted[row] - base[row]) / delta);
+        });
+
+        jacobian
+    }
+}
+
+impl<Float, const N: usize, const P: usize, Dynamics> ParametricSystem<Float, N, P> for Dynamics
+where
+    Dynamics: FnMut(Float, &[Float; N], &[Float; P]) -> [Float; N],
+{
+    fn dynamics(&mut self, time: Float, state: &[Float; N], parameters: &[Float; P]) -> [Float; N] {
+        self(time, state, parameters)
+    }
+}
+
+impl<Float, const N: usize, const P: usize, Dynamics> ParametricSystem<Float, N, P> for Autonomous<Dynamics>
+where
+    Dynamics: FnMut(&[Float; N], &[Float; P]) -> [Float; N],
+{
+    fn dynamics(&mut self, _time: Float, state: &[Float; N], parameters: &[Float; P]) -> [Float; N] {
+        (self.0)(state, parameters)
+    }
+}
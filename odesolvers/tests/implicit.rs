@@ -0,0 +1,27 @@
+use odesolvers::implicit::ImplicitIntegrator;
+use odesolvers::implicit::ImplicitMethod;
+
+// steps taken and the error against cos at the end of y'' = -y
+fn harmonic_run(method: ImplicitMethod, tolerance: f64, final_time: f64) -> (usize, f64) {
+    let mut integrator = ImplicitIntegrator::build([1., 0.], 0.1, |state: &[f64; 2]| [state[1], -state[0]]);
+    integrator.set_method(method).set_control().absolute_tolerance(tolerance).relative_tolerance(tolerance);
+    let steps = integrator.solve_until(final_time).unwrap().len();
+
+    (steps, (integrator.state()[0] - final_time.cos()).abs())
+}
+
+#[test]
+fn rosenbrock_tightens_with_the_tolerance_on_linear_problems() {
+    for method in [ImplicitMethod::Ros34pw2, ImplicitMethod::Rodas4] {
+        let (loose_steps, loose_error) = harmonic_run(method, 1e-6, 20.);
+        let (tight_steps, tight_error) = harmonic_run(method, 5e-7, 20.);
+        assert!(tight_steps > loose_steps, "{method:?} took {tight_steps} steps against {loose_steps}");
+        assert!(tight_error < loose_error, "{method:?} ended {tight_error} off against {loose_error}");
+    }
+}
+
+#[test]
+fn rosenbrock_meets_the_tolerance_backwards() {
+    let (_, error) = harmonic_run(ImplicitMethod::Ros34pw2, 1e-8, -5.);
+    assert!(error < 1e-6, "ended {error} off");
+}
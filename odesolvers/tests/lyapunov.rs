@@ -0,0 +1,50 @@
+use odesolvers::error::SolverError;
+use odesolvers::lyapunov::lyapunov_spectrum;
+use odesolvers::runge_kutta::Integrator;
+use odesolvers::system::OdeSystem;
+
+fn harmonic(delta_time: f64) -> Integrator<f64, 2, impl OdeSystem<f64, 2>> {
+    Integrator::build([1., 0.], delta_time, |state: &[f64; 2]| [state[1], -state[0]])
+}
+
+// stays put while the linearisation at both ends of a step of 6 adds up to squeezing the second tangent to zero,
+// the one halfway through is zero so rk4 leaves exactly I - e2 e2 behind
+struct Squeeze;
+
+impl OdeSystem<f64, 2> for Squeeze {
+    fn dynamics(&mut self, _time: f64, _state: &[f64; 2]) -> [f64; 2] {
+        [0., 0.]
+    }
+
+    fn jacobian(&mut self, time: f64, _state: &[f64; 2]) -> [[f64; 2]; 2] {
+        match time == 3. {
+            true => [[0., 0.], [0., 0.]],
+            false => [[0., 0.], [0., -0.5]],
+        }
+    }
+}
+
+#[test]
+fn durations_shorter_than_a_step_are_rejected() {
+    let result = lyapunov_spectrum(&mut harmonic(0.1), 0.04, 1);
+    assert_eq!(result.unwrap_err(), SolverError::InvalidDuration { duration: 0.04, step: 0.1 });
+}
+
+#[test]
+fn backward_spectra_are_rejected() {
+    let result = lyapunov_spectrum(&mut harmonic(-0.1), 10., 1);
+    assert_eq!(result.unwrap_err(), SolverError::BackwardIntegration { time: 0., final_time: -10. });
+
+    let result = lyapunov_spectrum(&mut harmonic(0.1), -10., 1);
+    assert_eq!(result.unwrap_err(), SolverError::BackwardIntegration { time: 0., final_time: -10. });
+
+    let result = lyapunov_spectrum(&mut harmonic(0.), 10., 1);
+    assert_eq!(result.unwrap_err(), SolverError::InvalidStepSize { step: 0. });
+}
+
+#[test]
+fn collapsed_tangents_are_reported() {
+    let mut integrator = Integrator::build_system([1., 1.], 6., Squeeze);
+    let result = lyapunov_spectrum(&mut integrator, 6., 1);
+    assert_eq!(result.unwrap_err(), SolverError::DegenerateTangents { time: 6. });
+}
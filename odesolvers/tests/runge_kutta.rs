@@ -0,0 +1,34 @@
+use odesolvers::runge_kutta::Integrator;
+use odesolvers::system::OdeSystem;
+
+// y'' = -y from cos, with tolerances well below the errors the tests look for
+
+fn harmonic(delta_time: f64) -> Integrator<f64, 2, impl OdeSystem<f64, 2>> {
+    let mut integrator = Integrator::build([1., 0.], delta_time, |state: &[f64; 2]| [state[1], -state[0]]);
+    integrator.set_control().absolute_tolerance(1e-10).relative_tolerance(1e-10);
+    integrator
+}
+
+#[test]
+fn adams_start_does_not_depend_on_the_initial_step() {
+    for delta_time in [0.01, 0.1, 0.5, 1.] {
+        let mut integrator = harmonic(delta_time);
+        integrator.solve_adams_until(10.).unwrap();
+        let error = (integrator.state()[0] - 10f64.cos()).abs();
+        assert!(error < 1e-9, "starting from {delta_time} ended {error} off");
+    }
+}
+
+#[test]
+fn output_times_just_past_a_step_land_despite_min_step() {
+    // loose enough that every step runs at max_step and leaves a remainder far below min_step
+    let mut integrator = Integrator::build([1., 0.], 0.1, |state: &[f64; 2]| [state[1], -state[0]]);
+    integrator.set_control().absolute_tolerance(1e-4).relative_tolerance(1e-4).max_step(0.1).min_step(1e-3);
+    let times: Vec<f64> = (1..=10).map(|idx| 0.1 * idx as f64 + 1e-5).collect();
+    let output = integrator.solve_at(&times).unwrap();
+    assert_eq!(output.len(), times.len());
+    output.iter().zip(&times).for_each(|(&(time, state), &expected)| {
+        assert_eq!(time, expected);
+        assert!((state[0] - time.cos()).abs() < 1e-4, "{time} ended {} off", (state[0] - time.cos()).abs());
+    });
+}
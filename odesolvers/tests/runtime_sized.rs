@@ -0,0 +1,85 @@
+use odesolvers::event::Direction;
+use odesolvers::event::RuntimeEvent;
+use odesolvers::runge_kutta::Integrator;
+use odesolvers::runtime_sized::RuntimeIntegrator;
+use odesolvers::system::OdeSystem;
+use odesolvers::system::RuntimeSystem;
+
+// y'' = -y from cos, once as a runtime sized state and once as a fixed size one
+
+fn harmonic() -> RuntimeIntegrator<f64, impl RuntimeSystem<f64>> {
+    let mut integrator =
+        RuntimeIntegrator::build(vec![1., 0.], 0.1, |state: &[f64]| vec![state[1], -state[0]]);
+    integrator.set_control().absolute_tolerance(1e-10).relative_tolerance(1e-10);
+    integrator
+}
+
+fn fixed_harmonic() -> Integrator<f64, 2, impl OdeSystem<f64, 2>> {
+    let mut integrator = Integrator::build([1., 0.], 0.1, |state: &[f64; 2]| [state[1], -state[0]]);
+    integrator.set_control().absolute_tolerance(1e-10).relative_tolerance(1e-10);
+    integrator
+}
+
+#[test]
+fn adams_and_extrapolation_match_the_fixed_size_integrator() {
+    let (mut runtime, mut fixed) = (harmonic(), fixed_harmonic());
+    let adams = runtime.solve_adams_with_time(10.).unwrap();
+    let expected = fixed.solve_adams_with_time(10.).unwrap();
+    assert_eq!(adams.len(), expected.len());
+    adams.iter().zip(&expected).for_each(|((time, state), (expected_time, expected_state))| {
+        assert_eq!((time, state.as_slice()), (expected_time, expected_state.as_slice()));
+    });
+
+    let (mut runtime, mut fixed) = (harmonic(), fixed_harmonic());
+    let extrapolated = runtime.solve_extrapolation_until(10.).unwrap();
+    let expected = fixed.solve_extrapolation_until(10.).unwrap();
+    assert_eq!(extrapolated.len(), expected.len());
+    assert_eq!(runtime.state(), fixed.state().as_slice());
+    assert!((runtime.state()[0] - 10f64.cos()).abs() < 1e-8);
+}
+
+#[test]
+fn dense_output_follows_the_solution_between_steps() {
+    let mut integrator = harmonic();
+    let solution = integrator.solve_dynamic_dense(10.).unwrap();
+    assert_eq!(solution.final_time(), 10.);
+    (0..=100).map(|idx| 0.1 * idx as f64).for_each(|time| {
+        let state = solution.evaluate(time).unwrap();
+        assert_eq!(state.len(), 2);
+        assert!((state[0] - time.cos()).abs() < 1e-6, "{time} ended {} off", (state[0] - time.cos()).abs());
+    });
+    assert!(solution.evaluate(10.5).is_none());
+}
+
+#[test]
+fn events_locate_crossings_and_stop_on_terminal_ones() {
+    let mut integrator = harmonic();
+    integrator.add_event(RuntimeEvent::build(|_, state: &[f64]| state[0]).direction(Direction::Falling));
+    integrator.add_event(
+        RuntimeEvent::build(|_, state: &[f64]| state[1]).direction(Direction::Rising).terminal(true),
+    );
+    integrator.solve_dynamic_until(10.).unwrap();
+
+    // the first zero of cos falls, the first zero of -sin rises at pi and ends the solve
+    let log = integrator.event_log();
+    assert_eq!(log.iter().map(|record| record.index).collect::<Vec<_>>(), [0, 1]);
+    assert!((log[0].time - std::f64::consts::FRAC_PI_2).abs() < 1e-8);
+    assert!((log[1].time - std::f64::consts::PI).abs() < 1e-8);
+    assert_eq!(integrator.curr_time(), log[1].time);
+    assert!((integrator.state()[0] + 1.).abs() < 1e-8);
+}
+
+#[test]
+fn sections_are_returned_from_the_runtime_integrator() {
+    let mut integrator = harmonic();
+    let section = RuntimeEvent::build(|_, state: &[f64]| state[0]).direction(Direction::Rising);
+    let crossings = integrator.solve_dynamic_section(20., section).unwrap();
+    // cos rises through zero at 3 pi / 2 + 2 pi k
+    assert_eq!(crossings.len(), 3);
+    crossings.iter().enumerate().for_each(|(idx, (time, state))| {
+        let expected = 1.5 * std::f64::consts::PI + 2. * std::f64::consts::PI * idx as f64;
+        assert!((time - expected).abs() < 1e-8);
+        assert!((state[1] - 1.).abs() < 1e-8);
+    });
+    assert!(integrator.event_log().is_empty());
+}
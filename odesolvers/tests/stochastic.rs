@@ -0,0 +1,35 @@
+use odesolvers::error::SolverError;
+use odesolvers::stochastic::StochasticIntegrator;
+
+fn decay(state: &[f64; 1]) -> [f64; 1] {
+    [-state[0]]
+}
+
+fn noise(_state: &[f64; 1]) -> [f64; 1] {
+    [0.1]
+}
+
+#[test]
+fn unusable_step_sizes_are_rejected() {
+    for delta_time in [0., f64::NAN, f64::INFINITY] {
+        let mut integrator = StochasticIntegrator::build([1.], delta_time, 7, decay, noise);
+        let result = integrator.solve_with_time(1.);
+        assert!(matches!(result, Err(SolverError::InvalidStepSize { .. })), "{delta_time} gave {result:?}");
+    }
+}
+
+#[test]
+fn step_count_is_bounded() {
+    let mut integrator = StochasticIntegrator::build([1.], 1e-9, 7, decay, noise);
+    integrator.set_control().max_steps(1000);
+    let result = integrator.solve_with_time(1.);
+    assert!(matches!(result, Err(SolverError::TooManySteps { steps: 1000, .. })), "gave {result:?}");
+}
+
+#[test]
+fn backward_solves_are_rejected() {
+    let mut integrator = StochasticIntegrator::build([1.], 0.01, 7, decay, noise);
+    integrator.set_time(1.);
+    let result = integrator.solve_with_time(0.);
+    assert!(matches!(result, Err(SolverError::BackwardIntegration { .. })), "gave {result:?}");
+}